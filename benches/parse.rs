@@ -0,0 +1,196 @@
+use chrono::{TimeZone, Utc};
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion, Throughput};
+use postgres_replication_types::{
+    pg_timestamp_to_micros, ByteReader, InsertMessage, RelationMessage, ReplicationMessage,
+    TupleData, TupleDataColumn, TupleDataKind, UpdateMessage,
+};
+
+fn encode(message: ReplicationMessage<'_>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    message.encode(&mut buf).unwrap();
+    buf
+}
+
+/// A row with `columns` text values.
+fn text_row(columns: usize) -> TupleData<'static> {
+    (0..columns)
+        .fold(TupleData::builder(), |builder, i| {
+            builder.text(format!("value of column {i}"))
+        })
+        .build()
+}
+
+/// Encodes an insert of a row with `columns` text values.
+fn text_insert(columns: usize) -> Vec<u8> {
+    let insert = InsertMessage::builder()
+        .oid(16385)
+        .data(text_row(columns))
+        .build();
+    encode(ReplicationMessage::Insert(insert))
+}
+
+fn update(columns: usize) -> Vec<u8> {
+    let update = UpdateMessage::builder()
+        .oid(16385)
+        .old(text_row(columns))
+        .new_tuple(text_row(columns))
+        .build();
+    encode(ReplicationMessage::Update(update))
+}
+
+fn relation(columns: usize) -> Vec<u8> {
+    let relation = (1..columns)
+        .fold(
+            RelationMessage::builder()
+                .oid(16385)
+                .name("events")
+                .key_column("id", 20),
+            |builder, i| builder.column(&format!("column_{i}"), 25),
+        )
+        .build();
+    encode(ReplicationMessage::Relation(relation))
+}
+
+fn parse(c: &mut Criterion) {
+    let cases = [
+        ("insert", text_insert(10)),
+        ("insert_50_columns", text_insert(50)),
+        ("insert_100_columns", text_insert(100)),
+        ("update", update(10)),
+        ("update_50_columns", update(50)),
+        ("relation", relation(10)),
+        ("relation_50_columns", relation(50)),
+        ("relation_100_columns", relation(100)),
+    ];
+
+    let mut group = c.benchmark_group("parse");
+    for (name, buf) in &cases {
+        group.throughput(Throughput::Bytes(buf.len() as u64));
+        group.bench_function(*name, |b| {
+            b.iter(|| ReplicationMessage::parse(black_box(buf)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn decode(c: &mut Criterion) {
+    let timestamp = Utc.with_ymd_and_hms(2024, 6, 15, 13, 45, 30).unwrap();
+    let binary_int = 42_i64.to_be_bytes();
+    let binary_timestamp = pg_timestamp_to_micros(timestamp).to_be_bytes();
+
+    let text = |value: &'static str| TupleDataColumn {
+        kind: TupleDataKind::Text(value.into()),
+    };
+    let binary_int = TupleDataColumn {
+        kind: TupleDataKind::Binary(&binary_int),
+    };
+    let binary_timestamp = TupleDataColumn {
+        kind: TupleDataKind::Binary(&binary_timestamp),
+    };
+
+    let mut group = c.benchmark_group("decode");
+    let int = text("4242424242");
+    group.bench_function("int_text", |b| b.iter(|| black_box(&int).as_i64().unwrap()));
+    group.bench_function("int_binary", |b| {
+        b.iter(|| black_box(&binary_int).as_i64().unwrap())
+    });
+    let value = text("value of column 1");
+    group.bench_function("text", |b| {
+        b.iter(|| black_box(&value).as_text().unwrap().len())
+    });
+    let timestamptz = text("2024-06-15 13:45:30.123456+00");
+    group.bench_function("timestamptz_text", |b| {
+        b.iter(|| black_box(&timestamptz).as_timestamptz().unwrap())
+    });
+    group.bench_function("timestamptz_binary", |b| {
+        b.iter(|| black_box(&binary_timestamp).as_timestamptz().unwrap())
+    });
+    group.finish();
+}
+
+/// Converting a message that owns its text, e.g. one built by hand, moves the text with
+/// `into_owned` but clones it with `to_owned`.
+fn owned(c: &mut Criterion) {
+    let insert = ReplicationMessage::Insert(
+        InsertMessage::builder()
+            .oid(16385)
+            .data(
+                (0..10)
+                    .fold(TupleData::builder(), |builder, _| {
+                        builder.text("x".repeat(64 * 1024))
+                    })
+                    .build(),
+            )
+            .build(),
+    );
+
+    let mut group = c.benchmark_group("owned");
+    group.bench_function("to_owned_wide_text", |b| {
+        b.iter(|| black_box(&insert).to_owned())
+    });
+    group.bench_function("into_owned_wide_text", |b| {
+        b.iter_batched(
+            || insert.clone(),
+            |insert| insert.into_owned(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+/// Skipping only reads the lengths, so it doesn't allocate the relation's strings.
+fn skip(c: &mut Criterion) {
+    let cases = [
+        ("insert_50_columns", text_insert(50)),
+        ("relation_50_columns", relation(50)),
+    ];
+
+    let mut group = c.benchmark_group("skip");
+    for (name, buf) in &cases {
+        group.throughput(Throughput::Bytes(buf.len() as u64));
+        group.bench_function(*name, |b| {
+            b.iter(|| ReplicationMessage::skip(black_box(buf)).unwrap())
+        });
+    }
+    group.finish();
+}
+
+/// Decodes the text columns of an insert into `columns`, like the parser does.
+fn read_columns<'a>(buf: &'a [u8], columns: &mut Vec<TupleDataColumn<'a>>) {
+    // Tag, relation OID, tuple tag and column count.
+    let mut reader = ByteReader::new(&buf[1 + 4 + 1..]);
+    for _ in 0..reader.read_i16().unwrap() {
+        reader.read_u8().unwrap();
+        let text = reader.read_nullable_bytes().unwrap().unwrap();
+        columns.push(TupleDataColumn {
+            kind: TupleDataKind::Text(std::str::from_utf8(text).unwrap().into()),
+        });
+    }
+}
+
+/// A 100-column row decoded into a vector growing as columns are read, as the parser did
+/// before it sized vectors up front from the column count, and into a vector sized up front.
+fn presize(c: &mut Criterion) {
+    let buf = text_insert(100);
+
+    let mut group = c.benchmark_group("presize");
+    group.throughput(Throughput::Bytes(buf.len() as u64));
+    group.bench_function("insert_100_columns_growing", |b| {
+        b.iter(|| {
+            let mut columns = Vec::new();
+            read_columns(black_box(&buf), &mut columns);
+            columns
+        })
+    });
+    group.bench_function("insert_100_columns_presized", |b| {
+        b.iter(|| {
+            let mut columns = Vec::with_capacity(100);
+            read_columns(black_box(&buf), &mut columns);
+            columns
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, parse, decode, owned, skip, presize);
+criterion_main!(benches);
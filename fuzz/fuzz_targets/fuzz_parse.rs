@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use postgres_replication_types::{
+    CopyData, MessageIter, ParseOptions, ProtocolVersion, ReplicationMessage,
+};
+
+const VERSIONS: [ProtocolVersion; 4] = [
+    ProtocolVersion::V1,
+    ProtocolVersion::V2,
+    ProtocolVersion::V3,
+    ProtocolVersion::V4,
+];
+
+// Parsing must never panic, whatever the input: it either succeeds or returns an error.
+fuzz_target!(|data: &[u8]| {
+    let _ = CopyData::parse(data);
+
+    for version in VERSIONS {
+        let _ = ReplicationMessage::parse_with_version(data, version);
+        let _ = ReplicationMessage::parse_tolerant(data, version);
+
+        for strict in [true, false] {
+            let options = ParseOptions { strict, version };
+            MessageIter::with_options(data, options).for_each(drop);
+        }
+    }
+});
@@ -0,0 +1,7 @@
+#![no_std]
+
+use postgres_replication_types::{ParseError, ReplicationMessage};
+
+pub fn parse(buf: &[u8]) -> Result<ReplicationMessage<'_>, ParseError> {
+    ReplicationMessage::parse(buf)
+}
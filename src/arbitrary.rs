@@ -0,0 +1,346 @@
+//! [`Arbitrary`] implementations generating messages that can be encoded and parsed back.
+//!
+//! The generated values respect the constraints of the wire format: strings don't contain NUL,
+//! GIDs are no longer than [`MAX_GID_LENGTH`], namespaces are never empty, timestamps have
+//! microsecond precision and fit into a [`DateTime`], the `length` of generic messages matches
+//! their content, and the abort LSN and timestamp of `StreamAbort` are either both present or
+//! both absent. Which optional xids are
+//! present depends on the protocol version, which isn't known here, so they're left arbitrary.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use ::arbitrary::{Arbitrary, Result, Unstructured};
+use chrono::{DateTime, Utc};
+
+use crate::timestamp::from_pg_micros;
+use crate::*;
+
+/// A string as sent NUL-terminated on the wire, which can't contain NUL.
+fn c_string(u: &mut Unstructured<'_>) -> Result<String> {
+    Ok(String::arbitrary(u)?.replace('\0', ""))
+}
+
+/// A GID of a prepared transaction, which can't be longer than [`MAX_GID_LENGTH`].
+fn gid(u: &mut Unstructured<'_>) -> Result<String> {
+    let mut gid = c_string(u)?;
+    let mut length = gid.len().min(MAX_GID_LENGTH);
+    while !gid.is_char_boundary(length) {
+        length -= 1;
+    }
+    gid.truncate(length);
+
+    Ok(gid)
+}
+
+/// A namespace, which is sent as an empty string for `pg_catalog` and parsed as `None`.
+fn namespace(u: &mut Unstructured<'_>) -> Result<Option<String>> {
+    Ok(Some(c_string(u)?).filter(|namespace| !namespace.is_empty()))
+}
+
+fn timestamp(u: &mut Unstructured<'_>) -> Result<DateTime<Utc>> {
+    // Keeps well within the range of `DateTime` on both sides of the Postgres epoch.
+    const BOUND: i64 = 1 << 62;
+
+    let micros = u.int_in_range(-BOUND..=BOUND)?;
+    Ok(from_pg_micros(micros).expect("timestamp within the range of DateTime"))
+}
+
+impl<'a> Arbitrary<'a> for Lsn {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u64::arbitrary(u).map(Self)
+    }
+}
+
+impl<'a> Arbitrary<'a> for Xid {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        u32::arbitrary(u).map(Self)
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedReplicationMessage {
+    /// Generates any message but [`OwnedReplicationMessage::Unknown`].
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=18)? {
+            0 => Self::Begin(u.arbitrary()?),
+            1 => Self::Generic(u.arbitrary()?),
+            2 => Self::Commit(u.arbitrary()?),
+            3 => Self::Origin(u.arbitrary()?),
+            4 => Self::Relation(u.arbitrary()?),
+            5 => Self::Type(u.arbitrary()?),
+            6 => Self::Insert(u.arbitrary()?),
+            7 => Self::Update(u.arbitrary()?),
+            8 => Self::Delete(u.arbitrary()?),
+            9 => Self::Truncate(u.arbitrary()?),
+            10 => Self::StreamStart(u.arbitrary()?),
+            11 => Self::StreamStop(StreamStopMessage {}),
+            12 => Self::StreamCommit(u.arbitrary()?),
+            13 => Self::StreamAbort(u.arbitrary()?),
+            14 => Self::BeginPrepare(u.arbitrary()?),
+            15 => Self::Prepare(u.arbitrary()?),
+            16 => Self::CommitPrepared(u.arbitrary()?),
+            17 => Self::RollbackPrepared(u.arbitrary()?),
+            _ => Self::StreamPrepare(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for BeginMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            final_lsn: u.arbitrary()?,
+            timestamp: timestamp(u)?,
+            transaction_id: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedGenericMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let transaction_id = u.arbitrary()?;
+        let is_transactional = u.arbitrary()?;
+        let lsn = u.arbitrary()?;
+        let prefix = c_string(u)?;
+        let content: Vec<u8> = u.arbitrary()?;
+
+        Ok(Self {
+            transaction_id,
+            is_transactional,
+            lsn,
+            prefix,
+            length: content.len() as i32,
+            content,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CommitMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            lsn: u.arbitrary()?,
+            final_lsn: u.arbitrary()?,
+            timestamp: timestamp(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OriginMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            lsn: u.arbitrary()?,
+            name: c_string(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RelationMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transaction_id: u.arbitrary()?,
+            oid: u.arbitrary()?,
+            namespace: namespace(u)?,
+            name: c_string(u)?,
+            replica_identity: u.arbitrary()?,
+            columns: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for ReplicaIdentity {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[Self::Default, Self::Nothing, Self::Full, Self::Index])?)
+    }
+}
+
+impl<'a> Arbitrary<'a> for RelationMessageColumn {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            is_part_of_the_key: u.arbitrary()?,
+            name: c_string(u)?,
+            oid: u.arbitrary()?,
+            type_modifier: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for TypeMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transaction_id: u.arbitrary()?,
+            oid: u.arbitrary()?,
+            namespace: namespace(u)?,
+            name: c_string(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedInsertMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transaction_id: u.arbitrary()?,
+            oid: u.arbitrary()?,
+            data: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedUpdateMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transaction_id: u.arbitrary()?,
+            oid: u.arbitrary()?,
+            key: u.arbitrary()?,
+            old: u.arbitrary()?,
+            new: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedDeleteMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transaction_id: u.arbitrary()?,
+            oid: u.arbitrary()?,
+            key: u.arbitrary()?,
+            old: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedTupleData {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            columns: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedTupleDataColumn {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            kind: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for OwnedTupleDataKind {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=3)? {
+            0 => Self::Null,
+            1 => Self::UnchangedToast,
+            2 => Self::Text(u.arbitrary()?),
+            _ => Self::Binary(u.arbitrary()?),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for TruncateMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transaction_id: u.arbitrary()?,
+            is_cascade: u.arbitrary()?,
+            is_restart_identity: u.arbitrary()?,
+            oids: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for StreamStartMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transaction_id: u.arbitrary()?,
+            is_first_segment: u.arbitrary()?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for StreamCommitMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            transaction_id: u.arbitrary()?,
+            lsn: u.arbitrary()?,
+            final_lsn: u.arbitrary()?,
+            timestamp: timestamp(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for StreamAbortMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let transaction_id = u.arbitrary()?;
+        let subtransaction_id = u.arbitrary()?;
+        let (abort_lsn, abort_timestamp) = if u.arbitrary()? {
+            (Some(u.arbitrary()?), Some(timestamp(u)?))
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            transaction_id,
+            subtransaction_id,
+            abort_lsn,
+            abort_timestamp,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for BeginPrepareMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            lsn: u.arbitrary()?,
+            final_lsn: u.arbitrary()?,
+            timestamp: timestamp(u)?,
+            transaction_id: u.arbitrary()?,
+            gid: gid(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for PrepareMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            lsn: u.arbitrary()?,
+            final_lsn: u.arbitrary()?,
+            timestamp: timestamp(u)?,
+            transaction_id: u.arbitrary()?,
+            gid: gid(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for CommitPreparedMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            lsn: u.arbitrary()?,
+            final_lsn: u.arbitrary()?,
+            timestamp: timestamp(u)?,
+            transaction_id: u.arbitrary()?,
+            gid: gid(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for RollbackPreparedMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            lsn: u.arbitrary()?,
+            final_lsn: u.arbitrary()?,
+            prepare_timestamp: timestamp(u)?,
+            timestamp: timestamp(u)?,
+            transaction_id: u.arbitrary()?,
+            gid: gid(u)?,
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for StreamPrepareMessage {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Self {
+            lsn: u.arbitrary()?,
+            final_lsn: u.arbitrary()?,
+            timestamp: timestamp(u)?,
+            transaction_id: u.arbitrary()?,
+            gid: gid(u)?,
+        })
+    }
+}
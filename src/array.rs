@@ -0,0 +1,260 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::value::FromValue;
+use crate::{TupleDataColumn, ValueDecodeError};
+
+/// A value of an array type of any number of dimensions, such as `int4[][]`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PgArray<T> {
+    /// Size of each dimension, outermost first. Empty for an empty array.
+    ///
+    /// NOTE: Lower bounds of the dimensions aren't kept.
+    pub dims: Vec<i32>,
+    /// The elements in row-major order, i.e. `{{1,2},{3,4}}` is `[1, 2, 3, 4]`. NULL elements
+    /// are `None`.
+    pub elements: Vec<Option<T>>,
+}
+
+impl TupleDataColumn<'_> {
+    /// Decodes a one-dimensional array value, such as `int4[]` or `text[]`.
+    ///
+    /// NULL elements are returned as `None`. Arrays of more dimensions are rejected, they can
+    /// be decoded with [`TupleDataColumn::as_multidim_array`].
+    pub fn as_array<T: FromValue>(&self) -> Result<Vec<Option<T>>, ValueDecodeError> {
+        let array = self.as_multidim_array()?;
+        if array.dims.len() > 1 {
+            return Err(invalid());
+        }
+
+        Ok(array.elements)
+    }
+
+    /// Decodes a one-dimensional `text[]` value.
+    pub fn as_text_array(&self) -> Result<Vec<Option<String>>, ValueDecodeError> {
+        self.as_array()
+    }
+
+    /// Decodes an array value of any number of dimensions, flattened along with its
+    /// dimensions.
+    pub fn as_multidim_array<T: FromValue>(&self) -> Result<PgArray<T>, ValueDecodeError> {
+        self.value()
+    }
+}
+
+impl<T: FromValue> FromValue for PgArray<T> {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        let PgArray { dims, elements } = parse_text(text)?;
+        let elements = elements
+            .into_iter()
+            .map(|element| element.as_deref().map(T::from_text).transpose())
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { dims, elements })
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        let PgArray { dims, elements } = parse_binary(bytes)?;
+        let elements = elements
+            .into_iter()
+            .map(|element| element.map(T::from_binary).transpose())
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { dims, elements })
+    }
+}
+
+fn invalid() -> ValueDecodeError {
+    ValueDecodeError::InvalidValue { type_name: "array" }
+}
+
+/// Maximum number of dimensions of an array (`MAXDIM` in Postgres).
+const MAX_DIMS: usize = 6;
+
+type Chars<'a> = core::iter::Peekable<core::str::Chars<'a>>;
+
+/// Splits the text array representation (e.g. `{{1,"a,b"},{NULL,2}}`) into its dimensions and
+/// unescaped elements.
+fn parse_text(text: &str) -> Result<PgArray<String>, ValueDecodeError> {
+    // Arrays with non-default lower bounds are prefixed with their dimensions, e.g. `[0:1]={1,2}`.
+    let text = match text.strip_prefix('[') {
+        Some(_) => text.split_once('=').ok_or_else(invalid)?.1,
+        None => text,
+    };
+
+    let mut chars = text.chars().peekable();
+    let mut dims = Vec::new();
+    let mut elements = Vec::new();
+    if chars.next() != Some('{') {
+        return Err(invalid());
+    }
+    while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+    if chars.next_if_eq(&'}').is_none() {
+        parse_text_level(&mut chars, 0, &mut dims, &mut elements)?;
+    }
+    if chars.next().is_some() {
+        return Err(invalid());
+    }
+
+    Ok(PgArray { dims, elements })
+}
+
+/// Parses the elements of a dimension at `depth` up to its closing brace, its opening brace
+/// being already read.
+///
+/// `dims` is filled with -1 once the number of dimensions is known from the first element, and
+/// each size is set when the first sub-array of its dimension ends; all others must match it.
+fn parse_text_level(
+    chars: &mut Chars<'_>,
+    depth: usize,
+    dims: &mut Vec<i32>,
+    elements: &mut Vec<Option<String>>,
+) -> Result<(), ValueDecodeError> {
+    let mut size = 0;
+    loop {
+        while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+
+        if chars.next_if_eq(&'{').is_some() {
+            if depth + 1 >= MAX_DIMS || (!dims.is_empty() && depth + 1 >= dims.len()) {
+                return Err(invalid());
+            }
+            parse_text_level(chars, depth + 1, dims, elements)?;
+        } else {
+            if dims.is_empty() {
+                dims.resize(depth + 1, -1);
+            }
+            if depth + 1 != dims.len() {
+                return Err(invalid());
+            }
+            elements.push(parse_text_element(chars)?);
+        }
+        size += 1;
+
+        while chars.next_if(|c| c.is_ascii_whitespace()).is_some() {}
+        match chars.next() {
+            Some(',') => continue,
+            Some('}') => break,
+            _ => return Err(invalid()),
+        }
+    }
+
+    match dims[depth] {
+        -1 => dims[depth] = size,
+        expected if expected != size => return Err(invalid()),
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Reads an element up to the next `,` or `}`, returning `None` for NULL.
+fn parse_text_element(chars: &mut Chars<'_>) -> Result<Option<String>, ValueDecodeError> {
+    let mut element = String::new();
+    if chars.next_if_eq(&'"').is_some() {
+        loop {
+            match chars.next().ok_or_else(invalid)? {
+                '"' => break,
+                '\\' => element.push(chars.next().ok_or_else(invalid)?),
+                c => element.push(c),
+            }
+        }
+
+        return Ok(Some(element));
+    }
+
+    while let Some(c) = chars.next_if(|&c| c != ',' && c != '}') {
+        match c {
+            '\\' => element.push(chars.next().ok_or_else(invalid)?),
+            '"' | '{' => return Err(invalid()),
+            c => element.push(c),
+        }
+    }
+    element.truncate(element.trim_end().len());
+    if element.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok((!element.eq_ignore_ascii_case("NULL")).then_some(element))
+}
+
+/// Splits the binary array representation into its dimensions and elements.
+///
+/// The format is a header of `ndim`, `has_null` flag and element type OID (4 bytes each),
+/// followed by a size and lower bound per dimension and the length-prefixed elements.
+fn parse_binary(bytes: &[u8]) -> Result<PgArray<&[u8]>, ValueDecodeError> {
+    let mut reader = BinaryReader::new(bytes, "array");
+
+    let ndim = reader.read_i32()?;
+    let _has_null = reader.read_i32()?;
+    let _element_type = reader.read_i32()?;
+    let mut dims = Vec::new();
+    let mut len = 1usize;
+    for _ in 0..ndim {
+        let size = reader.read_i32()?;
+        let _lower_bound = reader.read_i32()?;
+        len = usize::try_from(size)
+            .ok()
+            .and_then(|size| len.checked_mul(size))
+            .ok_or_else(invalid)?;
+        dims.push(size);
+    }
+    if dims.is_empty() {
+        len = 0;
+    }
+
+    let elements = (0..len)
+        .map(|_| reader.read_element())
+        .collect::<Result<_, _>>()?;
+    if !reader.bytes.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(PgArray { dims, elements })
+}
+
+/// A cursor over a binary value made of other values, such as an array or a record.
+pub(crate) struct BinaryReader<'a> {
+    pub(crate) bytes: &'a [u8],
+    /// Name of the Postgres type, reported when the value is malformed.
+    type_name: &'static str,
+}
+
+impl<'a> BinaryReader<'a> {
+    pub(crate) fn new(bytes: &'a [u8], type_name: &'static str) -> Self {
+        Self { bytes, type_name }
+    }
+
+    pub(crate) fn invalid(&self) -> ValueDecodeError {
+        ValueDecodeError::InvalidValue {
+            type_name: self.type_name,
+        }
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ValueDecodeError> {
+        if len > self.bytes.len() {
+            return Err(self.invalid());
+        }
+        let (bytes, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+
+        Ok(bytes)
+    }
+
+    pub(crate) fn read_i32(&mut self) -> Result<i32, ValueDecodeError> {
+        let bytes = self.read_bytes(4)?;
+
+        Ok(i32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Reads a length-prefixed element, where a length of `-1` means NULL.
+    pub(crate) fn read_element(&mut self) -> Result<Option<&'a [u8]>, ValueDecodeError> {
+        match self.read_i32()? {
+            -1 => Ok(None),
+            len => {
+                let len = usize::try_from(len).map_err(|_| self.invalid())?;
+                self.read_bytes(len).map(Some)
+            }
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+use ::arrow_array::builder::{
+    BinaryBuilder, BooleanBuilder, Date32Builder, Float32Builder, Float64Builder, Int16Builder,
+    Int32Builder, Int64Builder, StringBuilder, TimestampMicrosecondBuilder, UInt32Builder,
+};
+use ::arrow_array::{ArrayRef, RecordBatch};
+use ::arrow_schema::{DataType, Field, Schema, TimeUnit};
+use chrono::{NaiveDate, TimeZone, Utc};
+
+use crate::error::RecordBatchError;
+use crate::{
+    builtin_type_name, InsertMessage, RelationMessage, TupleDataColumn, ValueDecodeError, BOOL_OID,
+    BYTEA_OID, DATE_OID, FLOAT4_OID, FLOAT8_OID, INT2_OID, INT4_OID, INT8_OID, OID_OID,
+    TIMESTAMPTZ_OID, TIMESTAMP_OID,
+};
+
+/// Converts inserts of one relation to an Arrow [`RecordBatch`] with a column per relation column.
+///
+/// Columns of the built-in `bool`, `int2`, `int4`, `int8`, `oid`, `float4`, `float8`,
+/// `bytea`, `date`, `timestamp` and `timestamptz` types are decoded to the matching Arrow type.
+/// All other columns become `Utf8` columns holding the text format of the values, so their
+/// values must be sent in text format. NULL values become Arrow nulls; the fields are nullable
+/// as described by [`RelationMessage::schema`].
+pub fn inserts_to_record_batch(
+    relation: &RelationMessage,
+    rows: &[InsertMessage<'_>],
+) -> Result<RecordBatch, RecordBatchError> {
+    for row in rows {
+        if row.oid != relation.oid {
+            return Err(RecordBatchError::WrongRelation {
+                expected: relation.oid,
+                actual: row.oid,
+            });
+        }
+        relation.validate_tuple(&row.data)?;
+    }
+
+    let mut fields = Vec::with_capacity(relation.columns.len());
+    let mut arrays = Vec::with_capacity(relation.columns.len());
+    for (i, column) in relation.schema().into_iter().enumerate() {
+        let values = rows.iter().map(|row| &row.data.columns[i]);
+        let (data_type, array) =
+            convert(column.type_oid, values).map_err(|error| RecordBatchError::Decode {
+                column: column.name.clone(),
+                error,
+            })?;
+
+        fields.push(Field::new(column.name, data_type, column.nullable));
+        arrays.push(array);
+    }
+
+    Ok(RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?)
+}
+
+/// Decodes the values of a column with the given type OID to an Arrow array.
+fn convert<'a, 'b: 'a>(
+    type_oid: i32,
+    values: impl Iterator<Item = &'a TupleDataColumn<'b>>,
+) -> Result<(DataType, ArrayRef), ValueDecodeError> {
+    macro_rules! build {
+        ($builder:expr, $data_type:expr, $decode:expr) => {{
+            let mut builder = $builder;
+            for value in values {
+                if value.is_null() {
+                    builder.append_null();
+                } else {
+                    builder.append_value($decode(value)?);
+                }
+            }
+            ($data_type, Arc::new(builder.finish()) as ArrayRef)
+        }};
+    }
+
+    let unix_epoch = NaiveDate::from_ymd_opt(1970, 1, 1).unwrap();
+
+    Ok(match type_oid {
+        BOOL_OID => build!(
+            BooleanBuilder::new(),
+            DataType::Boolean,
+            TupleDataColumn::as_bool
+        ),
+        INT2_OID => build!(
+            Int16Builder::new(),
+            DataType::Int16,
+            TupleDataColumn::as_i16
+        ),
+        INT4_OID => build!(
+            Int32Builder::new(),
+            DataType::Int32,
+            TupleDataColumn::as_i32
+        ),
+        INT8_OID => build!(
+            Int64Builder::new(),
+            DataType::Int64,
+            TupleDataColumn::as_i64
+        ),
+        OID_OID => build!(
+            UInt32Builder::new(),
+            DataType::UInt32,
+            TupleDataColumn::as_oid
+        ),
+        FLOAT4_OID => build!(
+            Float32Builder::new(),
+            DataType::Float32,
+            TupleDataColumn::as_f32
+        ),
+        FLOAT8_OID => build!(
+            Float64Builder::new(),
+            DataType::Float64,
+            TupleDataColumn::as_f64
+        ),
+        BYTEA_OID => build!(
+            BinaryBuilder::new(),
+            DataType::Binary,
+            TupleDataColumn::as_bytes
+        ),
+        DATE_OID => build!(
+            Date32Builder::new(),
+            DataType::Date32,
+            |value: &TupleDataColumn<'b>| value
+                .as_date()
+                .map(|date| (date - unix_epoch).num_days() as i32)
+        ),
+        TIMESTAMP_OID => build!(
+            TimestampMicrosecondBuilder::new(),
+            DataType::Timestamp(TimeUnit::Microsecond, None),
+            |value: &TupleDataColumn<'b>| value
+                .as_timestamp()
+                .map(|timestamp| Utc.from_utc_datetime(&timestamp).timestamp_micros())
+        ),
+        TIMESTAMPTZ_OID => build!(
+            TimestampMicrosecondBuilder::new().with_timezone("UTC"),
+            DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+            |value: &TupleDataColumn<'b>| value
+                .as_timestamptz()
+                .map(|timestamp| timestamp.timestamp_micros())
+        ),
+        _ => {
+            let type_name = builtin_type_name(type_oid).unwrap_or("unknown");
+            build!(
+                StringBuilder::new(),
+                DataType::Utf8,
+                |value: &'a TupleDataColumn<'b>| { text(value, type_name) }
+            )
+        }
+    })
+}
+
+fn text<'a>(
+    value: &'a TupleDataColumn<'_>,
+    type_name: &'static str,
+) -> Result<&'a str, ValueDecodeError> {
+    if value.is_unchanged_toast() {
+        return Err(ValueDecodeError::UnchangedToast);
+    }
+    value
+        .as_text()
+        .ok_or(ValueDecodeError::InvalidValue { type_name })
+}
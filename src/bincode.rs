@@ -0,0 +1,44 @@
+use alloc::vec::Vec;
+
+use crate::*;
+
+macro_rules! bincode {
+    ($($type:ty),*) => {
+        $(
+            impl $type {
+                /// Serializes the value with [`bincode`](::bincode).
+                ///
+                /// The encoding is much more compact than JSON: integers and LSNs are written as
+                /// fixed-size numbers, binary values as plain bytes rather than base64, and
+                /// enum variants as indices rather than names.
+                pub fn to_bincode(&self) -> Result<Vec<u8>, ::bincode::Error> {
+                    ::bincode::serialize(self)
+                }
+
+                /// Deserializes a value serialized with `to_bincode`.
+                pub fn from_bincode(bytes: &[u8]) -> Result<Self, ::bincode::Error> {
+                    ::bincode::deserialize(bytes)
+                }
+            }
+        )*
+    };
+}
+
+bincode!(
+    OwnedReplicationMessage,
+    OwnedGenericMessage,
+    OwnedInsertMessage,
+    OwnedUpdateMessage,
+    OwnedDeleteMessage,
+    OwnedTupleData
+);
+
+impl ReplicationMessage<'_> {
+    /// Serializes the message with [`bincode`](::bincode), without copying it to an
+    /// [`OwnedReplicationMessage`] first.
+    ///
+    /// The result can be read back with [`OwnedReplicationMessage::from_bincode`].
+    pub fn to_bincode(&self) -> Result<Vec<u8>, ::bincode::Error> {
+        ::bincode::serialize(self)
+    }
+}
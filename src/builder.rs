@@ -0,0 +1,323 @@
+use alloc::borrow::{Cow, ToOwned};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{
+    DeleteMessage, InsertMessage, RelationMessage, RelationMessageColumn, ReplicaIdentity,
+    TupleData, TupleDataColumn, TupleDataKind, UpdateMessage, Xid,
+};
+
+/// Type modifier of columns whose type doesn't have one.
+const NO_TYPE_MODIFIER: i32 = -1;
+
+impl RelationMessage {
+    /// Starts building a relation in the `public` namespace.
+    pub fn builder() -> RelationMessageBuilder {
+        RelationMessageBuilder::default()
+    }
+}
+
+/// A builder of [`RelationMessage`]s, created with [`RelationMessage::builder`].
+#[derive(Debug, Clone)]
+pub struct RelationMessageBuilder {
+    message: RelationMessage,
+}
+
+impl Default for RelationMessageBuilder {
+    fn default() -> Self {
+        Self {
+            message: RelationMessage {
+                transaction_id: None,
+                oid: 0,
+                namespace: Some("public".to_owned()),
+                name: String::new(),
+                replica_identity: ReplicaIdentity::Default,
+                columns: Vec::new(),
+            },
+        }
+    }
+}
+
+impl RelationMessageBuilder {
+    /// Sets the xid of the streamed transaction.
+    pub fn transaction_id(mut self, xid: Xid) -> Self {
+        self.message.transaction_id = Some(xid);
+        self
+    }
+
+    /// Sets the OID of the relation.
+    pub fn oid(mut self, oid: i32) -> Self {
+        self.message.oid = oid;
+        self
+    }
+
+    /// Sets the namespace, `None` stands for `pg_catalog`.
+    pub fn namespace(mut self, namespace: Option<&str>) -> Self {
+        self.message.namespace = namespace.map(str::to_owned);
+        self
+    }
+
+    /// Sets the name of the relation.
+    pub fn name(mut self, name: &str) -> Self {
+        self.message.name = name.to_owned();
+        self
+    }
+
+    /// Sets the replica identity, [`ReplicaIdentity::Default`] if not set.
+    pub fn replica_identity(mut self, replica_identity: ReplicaIdentity) -> Self {
+        self.message.replica_identity = replica_identity;
+        self
+    }
+
+    /// Appends a column with the given data type OID and no type modifier.
+    pub fn column(self, name: &str, type_oid: i32) -> Self {
+        self.push_column(false, name, type_oid)
+    }
+
+    /// Appends a column that is part of the key.
+    pub fn key_column(self, name: &str, type_oid: i32) -> Self {
+        self.push_column(true, name, type_oid)
+    }
+
+    /// Appends a fully specified column.
+    pub fn column_with(mut self, column: RelationMessageColumn) -> Self {
+        self.message.columns.push(column);
+        self
+    }
+
+    /// Returns the built relation.
+    pub fn build(self) -> RelationMessage {
+        self.message
+    }
+
+    fn push_column(self, is_part_of_the_key: bool, name: &str, type_oid: i32) -> Self {
+        self.column_with(RelationMessageColumn {
+            is_part_of_the_key,
+            name: name.to_owned(),
+            oid: type_oid,
+            type_modifier: NO_TYPE_MODIFIER,
+        })
+    }
+}
+
+impl<'a> TupleData<'a> {
+    /// Starts building a tuple without columns.
+    pub fn builder() -> TupleDataBuilder<'a> {
+        TupleDataBuilder::default()
+    }
+}
+
+/// A builder of [`TupleData`], created with [`TupleData::builder`].
+#[derive(Debug, Clone, Default)]
+pub struct TupleDataBuilder<'a> {
+    columns: Vec<TupleDataColumn<'a>>,
+}
+
+impl<'a> TupleDataBuilder<'a> {
+    /// Appends a NULL value.
+    pub fn null(self) -> Self {
+        self.column(TupleDataKind::Null)
+    }
+
+    /// Appends an unchanged TOASTed value.
+    pub fn unchanged_toast(self) -> Self {
+        self.column(TupleDataKind::UnchangedToast)
+    }
+
+    /// Appends a value in text format.
+    pub fn text(self, value: impl Into<Cow<'a, str>>) -> Self {
+        self.column(TupleDataKind::Text(value.into()))
+    }
+
+    /// Appends a value in binary format.
+    pub fn binary(self, value: &'a [u8]) -> Self {
+        self.column(TupleDataKind::Binary(value))
+    }
+
+    /// Returns the built tuple.
+    pub fn build(self) -> TupleData<'a> {
+        TupleData {
+            columns: self.columns,
+        }
+    }
+
+    fn column(mut self, kind: TupleDataKind<'a>) -> Self {
+        self.columns.push(TupleDataColumn { kind });
+        self
+    }
+}
+
+impl<'a> InsertMessage<'a> {
+    /// Starts building an insert of an empty tuple.
+    pub fn builder() -> InsertMessageBuilder<'a> {
+        InsertMessageBuilder::default()
+    }
+}
+
+/// A builder of [`InsertMessage`]s, created with [`InsertMessage::builder`].
+#[derive(Debug, Clone)]
+pub struct InsertMessageBuilder<'a> {
+    message: InsertMessage<'a>,
+}
+
+impl Default for InsertMessageBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            message: InsertMessage {
+                transaction_id: None,
+                oid: 0,
+                data: TupleData {
+                    columns: Vec::new(),
+                },
+            },
+        }
+    }
+}
+
+impl<'a> InsertMessageBuilder<'a> {
+    /// Sets the xid of the streamed transaction.
+    pub fn transaction_id(mut self, xid: Xid) -> Self {
+        self.message.transaction_id = Some(xid);
+        self
+    }
+
+    /// Sets the OID of the relation.
+    pub fn oid(mut self, oid: i32) -> Self {
+        self.message.oid = oid;
+        self
+    }
+
+    /// Sets the new tuple.
+    pub fn data(mut self, data: TupleData<'a>) -> Self {
+        self.message.data = data;
+        self
+    }
+
+    /// Returns the built message.
+    pub fn build(self) -> InsertMessage<'a> {
+        self.message
+    }
+}
+
+impl<'a> UpdateMessage<'a> {
+    /// Starts building an update without old tuple data.
+    pub fn builder() -> UpdateMessageBuilder<'a> {
+        UpdateMessageBuilder::default()
+    }
+}
+
+/// A builder of [`UpdateMessage`]s, created with [`UpdateMessage::builder`].
+#[derive(Debug, Clone)]
+pub struct UpdateMessageBuilder<'a> {
+    message: UpdateMessage<'a>,
+}
+
+impl Default for UpdateMessageBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            message: UpdateMessage {
+                transaction_id: None,
+                oid: 0,
+                key: None,
+                old: None,
+                new: TupleData {
+                    columns: Vec::new(),
+                },
+            },
+        }
+    }
+}
+
+impl<'a> UpdateMessageBuilder<'a> {
+    /// Sets the xid of the streamed transaction.
+    pub fn transaction_id(mut self, xid: Xid) -> Self {
+        self.message.transaction_id = Some(xid);
+        self
+    }
+
+    /// Sets the OID of the relation.
+    pub fn oid(mut self, oid: i32) -> Self {
+        self.message.oid = oid;
+        self
+    }
+
+    /// Sets the old values of the replica identity columns.
+    pub fn key(mut self, key: TupleData<'a>) -> Self {
+        self.message.key = Some(key);
+        self
+    }
+
+    /// Sets the old tuple, as sent for REPLICA IDENTITY FULL.
+    pub fn old(mut self, old: TupleData<'a>) -> Self {
+        self.message.old = Some(old);
+        self
+    }
+
+    /// Sets the new tuple.
+    pub fn new_tuple(mut self, new: TupleData<'a>) -> Self {
+        self.message.new = new;
+        self
+    }
+
+    /// Returns the built message.
+    pub fn build(self) -> UpdateMessage<'a> {
+        self.message
+    }
+}
+
+impl<'a> DeleteMessage<'a> {
+    /// Starts building a delete without old tuple data.
+    pub fn builder() -> DeleteMessageBuilder<'a> {
+        DeleteMessageBuilder::default()
+    }
+}
+
+/// A builder of [`DeleteMessage`]s, created with [`DeleteMessage::builder`].
+#[derive(Debug, Clone)]
+pub struct DeleteMessageBuilder<'a> {
+    message: DeleteMessage<'a>,
+}
+
+impl Default for DeleteMessageBuilder<'_> {
+    fn default() -> Self {
+        Self {
+            message: DeleteMessage {
+                transaction_id: None,
+                oid: 0,
+                key: None,
+                old: None,
+            },
+        }
+    }
+}
+
+impl<'a> DeleteMessageBuilder<'a> {
+    /// Sets the xid of the streamed transaction.
+    pub fn transaction_id(mut self, xid: Xid) -> Self {
+        self.message.transaction_id = Some(xid);
+        self
+    }
+
+    /// Sets the OID of the relation.
+    pub fn oid(mut self, oid: i32) -> Self {
+        self.message.oid = oid;
+        self
+    }
+
+    /// Sets the old values of the replica identity columns.
+    pub fn key(mut self, key: TupleData<'a>) -> Self {
+        self.message.key = Some(key);
+        self
+    }
+
+    /// Sets the old tuple, as sent for REPLICA IDENTITY FULL.
+    pub fn old(mut self, old: TupleData<'a>) -> Self {
+        self.message.old = Some(old);
+        self
+    }
+
+    /// Returns the built message.
+    pub fn build(self) -> DeleteMessage<'a> {
+        self.message
+    }
+}
@@ -0,0 +1,180 @@
+use crate::{Lsn, ParseError, Xid};
+
+/// A bounds-checked cursor over a buffer of big-endian values, as sent by Postgres.
+///
+/// This is what the message parser is built on. It can be used to decode fields it doesn't know
+/// about, e.g. the payload of [`ReplicationMessage::Unknown`](crate::ReplicationMessage::Unknown).
+/// Every read either returns the value and advances past it or fails without advancing; offsets
+/// in errors are relative to the start of the buffer.
+#[derive(Debug, Clone)]
+pub struct ByteReader<'a> {
+    buf: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    /// Creates a reader positioned at the start of `buf`.
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf, pos: 0 }
+    }
+
+    /// Offset of the next byte to be read.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Number of bytes left to read.
+    pub fn remaining(&self) -> usize {
+        self.buf.len() - self.pos
+    }
+
+    /// Whether all bytes have been read.
+    pub fn is_empty(&self) -> bool {
+        self.remaining() == 0
+    }
+
+    /// Reads `len` bytes.
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], ParseError> {
+        if len > self.remaining() {
+            return Err(ParseError::UnexpectedEof {
+                offset: self.pos,
+                needed: len,
+                remaining: self.remaining(),
+            });
+        }
+
+        let bytes = &self.buf[self.pos..self.pos + len];
+        self.pos += len;
+
+        Ok(bytes)
+    }
+
+    /// Reads all bytes left.
+    pub fn read_rest(&mut self) -> &'a [u8] {
+        let bytes = &self.buf[self.pos..];
+        self.pos = self.buf.len();
+
+        bytes
+    }
+
+    fn read_array<const N: usize>(&mut self) -> Result<[u8; N], ParseError> {
+        let mut array = [0; N];
+        array.copy_from_slice(self.read_bytes(N)?);
+
+        Ok(array)
+    }
+
+    /// Reads a byte, failing with [`ParseError::UnexpectedEof`] without advancing if none is
+    /// left.
+    pub fn read_u8(&mut self) -> Result<u8, ParseError> {
+        Ok(self.read_array::<1>()?[0])
+    }
+
+    /// Reads a signed byte, failing with [`ParseError::UnexpectedEof`] without advancing if none
+    /// is left.
+    pub fn read_i8(&mut self) -> Result<i8, ParseError> {
+        Ok(i8::from_be_bytes(self.read_array()?))
+    }
+
+    /// Reads a big-endian Int16, failing with [`ParseError::UnexpectedEof`] without advancing if
+    /// fewer than 2 bytes are left.
+    pub fn read_i16(&mut self) -> Result<i16, ParseError> {
+        Ok(i16::from_be_bytes(self.read_array()?))
+    }
+
+    /// Reads a big-endian Int32, failing with [`ParseError::UnexpectedEof`] without advancing if
+    /// fewer than 4 bytes are left.
+    pub fn read_i32(&mut self) -> Result<i32, ParseError> {
+        Ok(i32::from_be_bytes(self.read_array()?))
+    }
+
+    /// Reads a big-endian unsigned 32-bit integer, failing with [`ParseError::UnexpectedEof`]
+    /// without advancing if fewer than 4 bytes are left.
+    pub fn read_u32(&mut self) -> Result<u32, ParseError> {
+        Ok(u32::from_be_bytes(self.read_array()?))
+    }
+
+    /// Reads a big-endian Int64, failing with [`ParseError::UnexpectedEof`] without advancing if
+    /// fewer than 8 bytes are left.
+    pub fn read_i64(&mut self) -> Result<i64, ParseError> {
+        Ok(i64::from_be_bytes(self.read_array()?))
+    }
+
+    /// Reads a big-endian unsigned 64-bit integer, failing with [`ParseError::UnexpectedEof`]
+    /// without advancing if fewer than 8 bytes are left.
+    pub fn read_u64(&mut self) -> Result<u64, ParseError> {
+        Ok(u64::from_be_bytes(self.read_array()?))
+    }
+
+    /// Reads a big-endian [`Lsn`], failing with [`ParseError::UnexpectedEof`] without advancing
+    /// if fewer than 8 bytes are left.
+    pub fn read_lsn(&mut self) -> Result<Lsn, ParseError> {
+        self.read_u64().map(Lsn)
+    }
+
+    /// Reads a big-endian [`Xid`], failing with [`ParseError::UnexpectedEof`] without advancing
+    /// if fewer than 4 bytes are left.
+    pub fn read_xid(&mut self) -> Result<Xid, ParseError> {
+        self.read_u32().map(Xid)
+    }
+
+    /// Reads an Int32 length followed by that many bytes, or `None` for a length of -1, which
+    /// stands for NULL. Other negative lengths are reported as [`ParseError::InvalidLength`].
+    pub fn read_nullable_bytes(&mut self) -> Result<Option<&'a [u8]>, ParseError> {
+        let offset = self.pos;
+        let length = self.read_i32()?;
+        if length == -1 {
+            return Ok(None);
+        }
+
+        let bytes = usize::try_from(length)
+            .map_err(|_| ParseError::InvalidLength { offset, length })
+            .and_then(|len| self.read_bytes(len));
+        if bytes.is_err() {
+            self.pos = offset;
+        }
+
+        bytes.map(Some)
+    }
+
+    /// Reads a NUL-terminated UTF-8 string, without the NUL.
+    pub fn read_cstr(&mut self) -> Result<&'a str, ParseError> {
+        let offset = self.pos;
+        let len = self.cstr_len()?;
+        let text = utf8(&self.buf[offset..offset + len], offset)?;
+        self.pos += len + 1;
+
+        Ok(text)
+    }
+
+    /// Advances past `len` bytes.
+    pub(crate) fn skip(&mut self, len: usize) -> Result<(), ParseError> {
+        self.read_bytes(len).map(|_| ())
+    }
+
+    /// Advances past a NUL-terminated string, without checking that it's valid UTF-8.
+    pub(crate) fn skip_cstr(&mut self) -> Result<(), ParseError> {
+        self.pos += self.cstr_len()? + 1;
+
+        Ok(())
+    }
+
+    /// Length of the NUL-terminated string at the current position, without the NUL.
+    fn cstr_len(&self) -> Result<usize, ParseError> {
+        self.buf[self.pos..]
+            .iter()
+            .position(|&byte| byte == 0)
+            .ok_or(ParseError::UnexpectedEof {
+                offset: self.pos,
+                needed: self.remaining() + 1,
+                remaining: self.remaining(),
+            })
+    }
+}
+
+/// Checks that `bytes` located at `offset` in the buffer are valid UTF-8.
+pub(crate) fn utf8(bytes: &[u8], offset: usize) -> Result<&str, ParseError> {
+    core::str::from_utf8(bytes).map_err(|err| ParseError::InvalidUtf8 {
+        offset: offset + err.valid_up_to(),
+    })
+}
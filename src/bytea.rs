@@ -0,0 +1,35 @@
+use alloc::borrow::Cow;
+use alloc::vec::Vec;
+
+use crate::{TupleDataColumn, TupleDataKind, ValueDecodeError};
+
+impl TupleDataColumn<'_> {
+    /// Decodes a `bytea` value.
+    ///
+    /// Binary values are borrowed as is. Text values are expected in the `hex` output format
+    /// (e.g. `\x48656c6c6f`), the default since Postgres 9.0.
+    pub fn as_bytes(&self) -> Result<Cow<'_, [u8]>, ValueDecodeError> {
+        match &self.kind {
+            TupleDataKind::Null => Err(ValueDecodeError::Null),
+            TupleDataKind::UnchangedToast => Err(ValueDecodeError::UnchangedToast),
+            TupleDataKind::Text(text) => decode_hex(text).map(Cow::Owned),
+            TupleDataKind::Binary(bytes) => Ok(Cow::Borrowed(bytes)),
+        }
+    }
+}
+
+fn decode_hex(text: &str) -> Result<Vec<u8>, ValueDecodeError> {
+    let invalid = || ValueDecodeError::InvalidValue { type_name: "bytea" };
+
+    let hex = text.strip_prefix("\\x").ok_or_else(invalid)?.as_bytes();
+    if hex.len() % 2 != 0 {
+        return Err(invalid());
+    }
+
+    hex.chunks_exact(2)
+        .map(|pair| {
+            let digit = |b: u8| char::from(b).to_digit(16).ok_or_else(invalid);
+            Ok((digit(pair[0])? << 4 | digit(pair[1])?) as u8)
+        })
+        .collect()
+}
@@ -0,0 +1,111 @@
+use alloc::vec::Vec;
+
+use crate::{
+    DeleteMessage, InsertMessage, ReplicationMessage, TruncateMessage, TupleData, UpdateMessage,
+};
+
+/// The kind of change of a [`ChangeEvent`] or [`TruncateEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Op {
+    Insert,
+    Update,
+    Delete,
+    Truncate,
+}
+
+/// A row change, giving [`InsertMessage`], [`UpdateMessage`] and [`DeleteMessage`] one shape.
+///
+/// Truncates don't change single rows of a single relation, so they're [`TruncateEvent`]s
+/// instead and a `ChangeEvent` is never an [`Op::Truncate`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ChangeEvent<'a> {
+    /// The kind of change.
+    pub op: Op,
+    /// OID of the relation.
+    pub relation_oid: i32,
+    /// The old row, or only its replica identity columns.
+    ///
+    /// `None` for inserts, and for updates that didn't change the replica identity of a relation
+    /// without REPLICA IDENTITY FULL.
+    pub before: Option<TupleData<'a>>,
+    /// The new row, `None` for deletes.
+    pub after: Option<TupleData<'a>>,
+}
+
+impl<'a> From<InsertMessage<'a>> for ChangeEvent<'a> {
+    fn from(message: InsertMessage<'a>) -> Self {
+        Self {
+            op: Op::Insert,
+            relation_oid: message.oid,
+            before: None,
+            after: Some(message.data),
+        }
+    }
+}
+
+impl<'a> From<UpdateMessage<'a>> for ChangeEvent<'a> {
+    fn from(message: UpdateMessage<'a>) -> Self {
+        Self {
+            op: Op::Update,
+            relation_oid: message.oid,
+            before: message.old.or(message.key),
+            after: Some(message.new),
+        }
+    }
+}
+
+impl<'a> From<DeleteMessage<'a>> for ChangeEvent<'a> {
+    fn from(message: DeleteMessage<'a>) -> Self {
+        Self {
+            op: Op::Delete,
+            relation_oid: message.oid,
+            before: message.old.or(message.key),
+            after: None,
+        }
+    }
+}
+
+/// A truncate of one or more relations, the sibling of [`ChangeEvent`] for [`TruncateMessage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TruncateEvent {
+    /// OIDs of the truncated relations.
+    pub relation_oids: Vec<i32>,
+    /// Is `CASCADE`?
+    pub is_cascade: bool,
+    /// Is `RESTART IDENTITY`?
+    pub is_restart_identity: bool,
+}
+
+impl TruncateEvent {
+    /// The kind of change, always [`Op::Truncate`].
+    pub fn op(&self) -> Op {
+        Op::Truncate
+    }
+}
+
+impl From<TruncateMessage> for TruncateEvent {
+    fn from(message: TruncateMessage) -> Self {
+        Self {
+            relation_oids: message.oids,
+            is_cascade: message.is_cascade,
+            is_restart_identity: message.is_restart_identity,
+        }
+    }
+}
+
+impl ReplicationMessage<'_> {
+    /// The kind of change of an insert, update, delete or truncate, `None` for other messages.
+    pub fn op(&self) -> Option<Op> {
+        match self {
+            Self::Insert(_) => Some(Op::Insert),
+            Self::Update(_) => Some(Op::Update),
+            Self::Delete(_) => Some(Op::Delete),
+            Self::Truncate(_) => Some(Op::Truncate),
+            _ => None,
+        }
+    }
+}
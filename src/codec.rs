@@ -0,0 +1,85 @@
+use bytes::{Buf, BytesMut};
+use tokio_util::codec::Decoder;
+
+use crate::{
+    OwnedReplicationMessage, ParseError, ParseOptions, ProtocolVersion, ReplicationMessage,
+};
+
+/// A [`Decoder`] of logical replication messages, for use with `tokio_util::codec::FramedRead`.
+///
+/// The leading xid is only read for messages between `StreamStart` and `StreamStop`, like in
+/// [`MessageIter`](crate::MessageIter).
+///
+/// NOTE: Messages aren't length-prefixed. With [`ParseOptions::parallel_streaming`], a
+/// `StreamAbort` (protocol version 4) cut off before its abort LSN and timestamp is waited for
+/// like any other partial message when `strict` is set. Otherwise the abort is decoded without
+/// the fields if they haven't been buffered yet, and their bytes are read as the next message.
+#[derive(Debug, Clone, Default)]
+pub struct ReplicationDecoder {
+    options: ParseOptions,
+    in_stream: bool,
+}
+
+impl ReplicationDecoder {
+    /// Creates a decoder of messages sent with protocol version 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a decoder of messages sent with the given protocol `version`.
+    pub fn with_version(version: ProtocolVersion) -> Self {
+        Self::with_options(ParseOptions {
+            version,
+            ..ParseOptions::default()
+        })
+    }
+
+    /// Creates a decoder parsing messages with the given [`ParseOptions`].
+    ///
+    /// NOTE: [`ReplicationMessage::Unknown`] consumes everything buffered so far.
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            options,
+            in_stream: false,
+        }
+    }
+
+    fn parse<'a>(&self, src: &'a [u8]) -> Result<(ReplicationMessage<'a>, usize), ParseError> {
+        ReplicationMessage::parse_prefix_in_stream(src, self.options, self.in_stream)
+    }
+}
+
+impl Decoder for ReplicationDecoder {
+    type Item = OwnedReplicationMessage;
+    type Error = ParseError;
+
+    fn decode(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        if src.is_empty() {
+            return Ok(None);
+        }
+
+        let (message, consumed) = match self.parse(src) {
+            Ok((message, consumed)) => (message.into_owned(), consumed),
+            Err(ParseError::UnexpectedEof { .. }) => return Ok(None),
+            Err(err) => return Err(err),
+        };
+        src.advance(consumed);
+
+        match message {
+            OwnedReplicationMessage::StreamStart(_) => self.in_stream = true,
+            OwnedReplicationMessage::StreamStop(_) => self.in_stream = false,
+            _ => {}
+        }
+
+        Ok(Some(message))
+    }
+
+    fn decode_eof(&mut self, src: &mut BytesMut) -> Result<Option<Self::Item>, Self::Error> {
+        match self.decode(src)? {
+            Some(message) => Ok(Some(message)),
+            None if src.is_empty() => Ok(None),
+            // The stream ended in the middle of a message, report where.
+            None => self.parse(src).map(|_| None),
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use alloc::vec::Vec;
+
+use chrono::{DateTime, Utc};
+
+use crate::parse::Reader;
+use crate::{timestamp, Lsn, ParseError, ProtocolVersion};
+
+/// A `CopyData` frame of the `START_REPLICATION` streaming protocol, sent by the server.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CopyData<'a> {
+    XLogData(XLogData<'a>),
+    PrimaryKeepalive(PrimaryKeepalive),
+}
+
+/// A frame carrying WAL data (`'w'`), i.e. a logical replication message for pgoutput.
+#[derive(Clone)]
+pub struct XLogData<'a> {
+    /// The starting point of the WAL data in this message.
+    pub wal_start: Lsn,
+    /// The current end of WAL on the server.
+    pub wal_end: Lsn,
+    /// The server's system clock at the time of transmission.
+    pub clock: DateTime<Utc>,
+    /// The WAL data, which can be parsed with [`ReplicationMessage::parse`](crate::ReplicationMessage::parse).
+    pub payload: &'a [u8],
+}
+
+/// A keepalive frame (`'k'`).
+#[derive(Debug, Clone)]
+pub struct PrimaryKeepalive {
+    /// The current end of WAL on the server.
+    pub wal_end: Lsn,
+    /// The server's system clock at the time of transmission.
+    pub clock: DateTime<Utc>,
+    /// Should the client reply to this message as soon as possible, to avoid a timeout disconnect?
+    pub reply_requested: bool,
+}
+
+impl<'a> CopyData<'a> {
+    /// Parses the payload of a `CopyData` message received during `START_REPLICATION`.
+    ///
+    /// The frame type is told by the first byte, `'w'` for [`XLogData`] and `'k'` for
+    /// [`PrimaryKeepalive`]; any other byte is reported as [`ParseError::UnknownMessageType`].
+    /// The WAL data of [`XLogData`] points into `buf`, while bytes after a [`PrimaryKeepalive`]
+    /// are reported as [`ParseError::TrailingBytes`].
+    pub fn parse(buf: &'a [u8]) -> Result<CopyData<'a>, ParseError> {
+        let mut reader = Reader::new(buf, ProtocolVersion::V1);
+
+        let frame = match reader.read_u8()? {
+            b'w' => Self::XLogData(XLogData {
+                wal_start: reader.read_lsn()?,
+                wal_end: reader.read_lsn()?,
+                clock: reader.read_timestamp()?,
+                payload: reader.read_rest(),
+            }),
+            b'k' => {
+                let keepalive = PrimaryKeepalive {
+                    wal_end: reader.read_lsn()?,
+                    clock: reader.read_timestamp()?,
+                    reply_requested: reader.read_bool()?,
+                };
+                if !reader.is_empty() {
+                    return Err(ParseError::TrailingBytes {
+                        offset: reader.position(),
+                        count: reader.remaining(),
+                    });
+                }
+
+                Self::PrimaryKeepalive(keepalive)
+            }
+            tag => return Err(ParseError::UnknownMessageType(tag)),
+        };
+
+        Ok(frame)
+    }
+}
+
+impl<'a> TryFrom<&'a [u8]> for CopyData<'a> {
+    type Error = ParseError;
+
+    /// Parses the frame with [`CopyData::parse`].
+    fn try_from(buf: &'a [u8]) -> Result<Self, Self::Error> {
+        Self::parse(buf)
+    }
+}
+
+#[cfg(feature = "tokio-postgres")]
+impl<'a> TryFrom<&'a bytes::Bytes> for CopyData<'a> {
+    type Error = ParseError;
+
+    /// Parses a frame of the `CopyBothDuplex` stream of tokio-postgres with
+    /// [`CopyData::parse`], the WAL data pointing into `bytes`.
+    fn try_from(bytes: &'a bytes::Bytes) -> Result<Self, Self::Error> {
+        Self::parse(bytes)
+    }
+}
+
+/// A standby status update (`'r'`), sent by the client to report replication progress.
+///
+/// WAL up to `flushed_lsn` can be removed by the server once it's no longer needed.
+#[derive(Debug, Clone)]
+pub struct StandbyStatusUpdate {
+    /// The location of the last WAL byte + 1 received and written to disk in the standby.
+    pub written_lsn: Lsn,
+    /// The location of the last WAL byte + 1 flushed to disk in the standby.
+    pub flushed_lsn: Lsn,
+    /// The location of the last WAL byte + 1 applied in the standby.
+    pub applied_lsn: Lsn,
+    /// The client's system clock at the time of transmission.
+    pub clock: DateTime<Utc>,
+    /// Should the server reply to this message immediately?
+    pub reply_requested: bool,
+}
+
+impl StandbyStatusUpdate {
+    /// Length of an encoded standby status update.
+    pub const LEN: usize = 34;
+
+    /// Encodes the payload of the `CopyData` message, appending it to `buf`.
+    pub fn encode(&self, buf: &mut Vec<u8>) {
+        buf.reserve(Self::LEN);
+        buf.push(b'r');
+        buf.extend_from_slice(&self.written_lsn.0.to_be_bytes());
+        buf.extend_from_slice(&self.flushed_lsn.0.to_be_bytes());
+        buf.extend_from_slice(&self.applied_lsn.0.to_be_bytes());
+        buf.extend_from_slice(&timestamp::pg_timestamp_to_micros(self.clock).to_be_bytes());
+        buf.push(u8::from(self.reply_requested));
+    }
+}
@@ -0,0 +1,81 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine;
+use serde_json::{json, Map, Value};
+
+use crate::{
+    ChangeEvent, Op, RelationMessage, TupleData, TupleDataColumn, TupleDataKind, BOOL_OID,
+    FLOAT4_OID, FLOAT8_OID, INT2_OID, INT4_OID, INT8_OID, OID_OID,
+};
+
+impl ChangeEvent<'_> {
+    /// Converts the event to the JSON envelope of Debezium's Postgres connector
+    /// (`before`, `after`, `op` and `source`).
+    ///
+    /// Columns are named after the `relation` the event belongs to. Integers, floats and
+    /// booleans become JSON numbers and booleans, binary values base64 strings, and all other
+    /// values are kept in their text format. Unchanged TOASTed values are omitted.
+    pub fn to_debezium_json(&self, relation: &RelationMessage) -> Value {
+        let op = match self.op {
+            Op::Insert => "c",
+            Op::Update => "u",
+            Op::Delete => "d",
+            Op::Truncate => "t",
+        };
+        let row = |tuple: &Option<TupleData>| match tuple {
+            Some(tuple) => Value::Object(debezium_row(relation, tuple)),
+            None => Value::Null,
+        };
+
+        json!({
+            "before": row(&self.before),
+            "after": row(&self.after),
+            "op": op,
+            "source": {
+                "connector": "postgresql",
+                "schema": relation.namespace.as_deref().unwrap_or("pg_catalog"),
+                "table": relation.name,
+            },
+        })
+    }
+}
+
+/// Names the values of the tuple. A `before` tuple holding only the key values is paired with the
+/// key columns, like `TupleData::key_columns`.
+fn debezium_row(relation: &RelationMessage, tuple: &TupleData) -> Map<String, Value> {
+    let columns: Vec<_> = if tuple.columns.len() == relation.columns.len() {
+        relation.columns.iter().zip(&tuple.columns).collect()
+    } else {
+        tuple.key_columns(relation)
+    };
+
+    columns
+        .into_iter()
+        .filter(|(_, value)| !value.is_unchanged_toast())
+        .map(|(column, value)| (column.name.clone(), debezium_value(column.oid, value)))
+        .collect()
+}
+
+fn debezium_value(type_oid: i32, column: &TupleDataColumn) -> Value {
+    let text = match &column.kind {
+        TupleDataKind::Text(text) => text,
+        TupleDataKind::Binary(bytes) => return Value::String(STANDARD.encode(bytes)),
+        TupleDataKind::Null | TupleDataKind::UnchangedToast => return Value::Null,
+    };
+
+    let typed = match type_oid {
+        BOOL_OID => column.as_bool().ok().map(Value::Bool),
+        INT2_OID | INT4_OID | INT8_OID | OID_OID => column.as_i64().ok().map(Value::from),
+        // Non-finite floats have no JSON representation and stay in text format.
+        FLOAT4_OID | FLOAT8_OID => column
+            .as_f64()
+            .ok()
+            .and_then(serde_json::Number::from_f64)
+            .map(Value::Number),
+        _ => None,
+    };
+
+    typed.unwrap_or_else(|| Value::String(text.to_string()))
+}
@@ -0,0 +1,94 @@
+//! [`Debug`] implementations that keep large values out of logs.
+//!
+//! Binary values and message contents are printed as their length only, and text values longer
+//! than [`TEXT_LIMIT`] bytes are cut short with an ellipsis and their full length.
+
+use core::fmt;
+
+use crate::{GenericMessage, OwnedGenericMessage, OwnedTupleDataKind, TupleDataKind, XLogData};
+
+/// Maximum length in bytes of the text values printed in full.
+const TEXT_LIMIT: usize = 64;
+
+struct Bytes<'a>(&'a [u8]);
+
+impl fmt::Debug for Bytes<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "<{} bytes>", self.0.len())
+    }
+}
+
+struct Text<'a>(&'a str);
+
+impl fmt::Debug for Text<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.len() <= TEXT_LIMIT {
+            return fmt::Debug::fmt(self.0, f);
+        }
+
+        let mut length = TEXT_LIMIT;
+        while !self.0.is_char_boundary(length) {
+            length -= 1;
+        }
+        write!(f, "{:?}… <{} bytes>", &self.0[..length], self.0.len())
+    }
+}
+
+impl fmt::Debug for TupleDataKind<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("Null"),
+            Self::UnchangedToast => f.write_str("UnchangedToast"),
+            Self::Text(value) => f.debug_tuple("Text").field(&Text(value)).finish(),
+            Self::Binary(value) => f.debug_tuple("Binary").field(&Bytes(value)).finish(),
+        }
+    }
+}
+
+impl fmt::Debug for OwnedTupleDataKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("Null"),
+            Self::UnchangedToast => f.write_str("UnchangedToast"),
+            Self::Text(value) => f.debug_tuple("Text").field(&Text(value)).finish(),
+            Self::Binary(value) => f.debug_tuple("Binary").field(&Bytes(value)).finish(),
+        }
+    }
+}
+
+impl fmt::Debug for GenericMessage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GenericMessage")
+            .field("transaction_id", &self.transaction_id)
+            .field("is_transactional", &self.is_transactional)
+            .field("lsn", &self.lsn)
+            .field("prefix", &Text(&self.prefix))
+            .field("length", &self.length)
+            .field("content", &Bytes(self.content))
+            .finish()
+    }
+}
+
+impl fmt::Debug for OwnedGenericMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedGenericMessage")
+            .field("transaction_id", &self.transaction_id)
+            .field("is_transactional", &self.is_transactional)
+            .field("lsn", &self.lsn)
+            .field("prefix", &Text(&self.prefix))
+            .field("length", &self.length)
+            .field("content", &Bytes(&self.content))
+            .finish()
+    }
+}
+
+impl fmt::Debug for XLogData<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("XLogData")
+            .field("wal_start", &self.wal_start)
+            .field("wal_end", &self.wal_end)
+            .field("clock", &self.clock)
+            .field("payload", &Bytes(self.payload))
+            .finish()
+    }
+}
@@ -0,0 +1,87 @@
+use rust_decimal::Decimal;
+
+use crate::value::FromValue;
+use crate::{TupleDataColumn, ValueDecodeError};
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+
+impl TupleDataColumn<'_> {
+    /// Decodes a `numeric` value.
+    ///
+    /// `NaN` and infinite values can't be represented by [`Decimal`] and are rejected.
+    pub fn as_decimal(&self) -> Result<Decimal, ValueDecodeError> {
+        self.value()
+    }
+}
+
+impl FromValue for Decimal {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        text.parse().map_err(ValueDecodeError::ParseDecimal)
+    }
+
+    /// Decodes the binary numeric format: a header of `ndigits`, `weight`, `sign` and `dscale`
+    /// (2 bytes each) followed by `ndigits` base-10000 digit groups.
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        let invalid = || ValueDecodeError::InvalidValue {
+            type_name: "numeric",
+        };
+        let word = |index: usize| {
+            bytes
+                .get(index * 2..index * 2 + 2)
+                .map(|word| u16::from_be_bytes([word[0], word[1]]))
+                .ok_or(ValueDecodeError::UnexpectedLength {
+                    expected: index * 2 + 2,
+                    actual: bytes.len(),
+                })
+        };
+
+        let ndigits = usize::from(word(0)?);
+        let weight = i64::from(word(1)? as i16);
+        let sign = word(2)?;
+        let dscale = u32::from(word(3)?);
+        if bytes.len() != 8 + ndigits * 2 {
+            return Err(ValueDecodeError::UnexpectedLength {
+                expected: 8 + ndigits * 2,
+                actual: bytes.len(),
+            });
+        }
+        if sign != NUMERIC_POS && sign != NUMERIC_NEG {
+            return Err(invalid());
+        }
+
+        let mut mantissa: i128 = 0;
+        for index in 0..ndigits {
+            let digit = word(4 + index)?;
+            if digit >= 10_000 {
+                return Err(invalid());
+            }
+            mantissa = mantissa
+                .checked_mul(10_000)
+                .and_then(|mantissa| mantissa.checked_add(i128::from(digit)))
+                .ok_or_else(invalid)?;
+        }
+
+        // Exponent (in digit groups) of the last digit group.
+        let exponent = weight - ndigits as i64 + 1;
+        let scale = if exponent >= 0 {
+            for _ in 0..exponent {
+                mantissa = mantissa.checked_mul(10_000).ok_or_else(invalid)?;
+            }
+            0
+        } else {
+            u32::try_from(-exponent * 4).map_err(|_| invalid())?
+        };
+        if sign == NUMERIC_NEG {
+            mantissa = -mantissa;
+        }
+
+        let mut decimal = Decimal::try_from_i128_with_scale(mantissa, scale)
+            .map_err(ValueDecodeError::ParseDecimal)?;
+        if dscale <= Decimal::MAX_SCALE {
+            decimal.rescale(dscale);
+        }
+
+        Ok(decimal)
+    }
+}
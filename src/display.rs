@@ -0,0 +1,169 @@
+//! One-line summaries of messages, meant for logs.
+//!
+//! Every summary starts with the upper-case message type, followed by `key=value` pairs.
+//! Column values and message contents are never printed, only their counts and lengths.
+
+use alloc::string::String;
+use core::fmt;
+
+use crate::{ReplicationMessage, TupleData, Xid};
+
+struct StreamedXid(Option<Xid>);
+
+impl fmt::Display for StreamedXid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(xid) => write!(f, " xid={xid}"),
+            None => Ok(()),
+        }
+    }
+}
+
+/// A name quoted and escaped like the other identifiers, so that neither a `.` nor a newline in
+/// it can be mistaken for the separator of its namespace or the end of the summary.
+struct QualifiedName<'a>(&'a Option<String>, &'a str);
+
+impl fmt::Display for QualifiedName<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Some(namespace) => write!(f, "{namespace:?}.{:?}", self.1),
+            None => write!(f, "{:?}", self.1),
+        }
+    }
+}
+
+struct OldTuple<'a, 'b>(&'a Option<TupleData<'b>>, &'a Option<TupleData<'b>>);
+
+impl fmt::Display for OldTuple<'_, '_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(key) = self.0 {
+            write!(f, " key={}", key.columns.len())?;
+        }
+        if let Some(old) = self.1 {
+            write!(f, " old={}", old.columns.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ReplicationMessage<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Begin(m) => write!(
+                f,
+                "BEGIN xid={} final_lsn={}",
+                m.transaction_id, m.final_lsn
+            ),
+            Self::Generic(m) => write!(
+                f,
+                "MESSAGE lsn={} prefix={:?} len={} transactional={}{}",
+                m.lsn,
+                m.prefix,
+                m.content.len(),
+                m.is_transactional,
+                StreamedXid(m.transaction_id)
+            ),
+            Self::Commit(m) => write!(f, "COMMIT lsn={}", m.lsn),
+            Self::Origin(m) => write!(f, "ORIGIN lsn={} name={:?}", m.lsn, m.name),
+            Self::Relation(m) => write!(
+                f,
+                "RELATION rel={} name={} cols={}{}",
+                m.oid,
+                QualifiedName(&m.namespace, &m.name),
+                m.columns.len(),
+                StreamedXid(m.transaction_id)
+            ),
+            Self::Type(m) => write!(
+                f,
+                "TYPE oid={} name={}{}",
+                m.oid,
+                QualifiedName(&m.namespace, &m.name),
+                StreamedXid(m.transaction_id)
+            ),
+            Self::Insert(m) => write!(
+                f,
+                "INSERT rel={} cols={}{}",
+                m.oid,
+                m.data.columns.len(),
+                StreamedXid(m.transaction_id)
+            ),
+            Self::Update(m) => write!(
+                f,
+                "UPDATE rel={} cols={}{}{}",
+                m.oid,
+                m.new.columns.len(),
+                OldTuple(&m.key, &m.old),
+                StreamedXid(m.transaction_id)
+            ),
+            Self::Delete(m) => write!(
+                f,
+                "DELETE rel={}{}{}",
+                m.oid,
+                OldTuple(&m.key, &m.old),
+                StreamedXid(m.transaction_id)
+            ),
+            Self::Truncate(m) => {
+                f.write_str("TRUNCATE rels=")?;
+                for (i, oid) in m.oids.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(",")?;
+                    }
+                    write!(f, "{oid}")?;
+                }
+                if m.is_cascade {
+                    f.write_str(" cascade")?;
+                }
+                if m.is_restart_identity {
+                    f.write_str(" restart_identity")?;
+                }
+                write!(f, "{}", StreamedXid(m.transaction_id))
+            }
+            Self::StreamStart(m) => write!(
+                f,
+                "STREAM_START first={}{}",
+                m.is_first_segment,
+                StreamedXid(m.transaction_id)
+            ),
+            Self::StreamStop(_) => f.write_str("STREAM_STOP"),
+            Self::StreamCommit(m) => {
+                write!(f, "STREAM_COMMIT xid={} lsn={}", m.transaction_id, m.lsn)
+            }
+            Self::StreamAbort(m) => write!(
+                f,
+                "STREAM_ABORT xid={} subxid={}",
+                m.transaction_id, m.subtransaction_id
+            ),
+            Self::BeginPrepare(m) => write!(
+                f,
+                "BEGIN_PREPARE xid={} lsn={} gid={:?}",
+                m.transaction_id, m.lsn, m.gid
+            ),
+            Self::Prepare(m) => write!(
+                f,
+                "PREPARE xid={} lsn={} gid={:?}",
+                m.transaction_id, m.lsn, m.gid
+            ),
+            Self::CommitPrepared(m) => write!(
+                f,
+                "COMMIT_PREPARED xid={} lsn={} gid={:?}",
+                m.transaction_id, m.lsn, m.gid
+            ),
+            Self::RollbackPrepared(m) => write!(
+                f,
+                "ROLLBACK_PREPARED xid={} lsn={} gid={:?}",
+                m.transaction_id, m.lsn, m.gid
+            ),
+            Self::StreamPrepare(m) => write!(
+                f,
+                "STREAM_PREPARE xid={} lsn={} gid={:?}",
+                m.transaction_id, m.lsn, m.gid
+            ),
+            Self::Unknown { tag, payload } => write!(
+                f,
+                "UNKNOWN tag={:?} len={}",
+                char::from(*tag),
+                payload.len()
+            ),
+        }
+    }
+}
@@ -0,0 +1,448 @@
+use chrono::{DateTime, Utc};
+
+use crate::*;
+
+impl ReplicationMessage<'_> {
+    /// Encodes the message into the pgoutput wire format, appending it to `buf`.
+    ///
+    /// This is the inverse of [`ReplicationMessage::parse_with_version`]: the leading xid of
+    /// streamed messages is written only if `transaction_id` is set, so such messages must be
+    /// parsed back with [`ProtocolVersion::V2`] or later.
+    ///
+    /// Fails with an [`EncodeError`] if a count or length doesn't fit its wire field, in which
+    /// case `buf` is left unchanged.
+    pub fn encode(&self, buf: &mut Vec<u8>) -> Result<(), EncodeError> {
+        Writer::write(buf, self)
+    }
+}
+
+impl OwnedReplicationMessage {
+    /// Encodes the message into the pgoutput wire format, appending it to `buf`.
+    ///
+    /// See [`ReplicationMessage::encode`].
+    pub fn encode(&self, buf: &mut Vec<u8>) -> Result<(), EncodeError> {
+        Writer::write(buf, self)
+    }
+}
+
+/// A whole message, tag included, implemented by both message enums.
+trait EncodeMessage {
+    fn encode_message(&self, writer: &mut Writer) -> Result<(), EncodeError>;
+}
+
+/// Implements [`EncodeMessage`] for the message enums, whose variants and fields have the same
+/// names, so that the wire format is written once for both.
+macro_rules! impl_encode_message {
+    ($($ty:ty),*) => {
+        $(
+            impl EncodeMessage for $ty {
+                fn encode_message(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+                    match self {
+                        Self::Begin(message) => writer.put_message(b'B', message)?,
+                        Self::Generic(message) => {
+                            writer.put_u8(b'M');
+                            writer.put_generic(
+                                message.transaction_id,
+                                message.is_transactional,
+                                message.lsn,
+                                &message.prefix,
+                                &message.content,
+                            )?;
+                        }
+                        Self::Commit(message) => writer.put_message(b'C', message)?,
+                        Self::Origin(message) => writer.put_message(b'O', message)?,
+                        Self::Relation(message) => writer.put_message(b'R', message)?,
+                        Self::Type(message) => writer.put_message(b'Y', message)?,
+                        Self::Insert(message) => {
+                            writer.put_u8(b'I');
+                            writer.put_streamed_xid(message.transaction_id);
+                            writer.put_i32(message.oid);
+                            writer.put_tuple(b'N', &message.data.columns)?;
+                        }
+                        Self::Update(message) => {
+                            writer.put_u8(b'U');
+                            writer.put_streamed_xid(message.transaction_id);
+                            writer.put_i32(message.oid);
+                            if let Some(key) = &message.key {
+                                writer.put_tuple(b'K', &key.columns)?;
+                            }
+                            if let Some(old) = &message.old {
+                                writer.put_tuple(b'O', &old.columns)?;
+                            }
+                            writer.put_tuple(b'N', &message.new.columns)?;
+                        }
+                        Self::Delete(message) => {
+                            writer.put_u8(b'D');
+                            writer.put_streamed_xid(message.transaction_id);
+                            writer.put_i32(message.oid);
+                            if let Some(key) = &message.key {
+                                writer.put_tuple(b'K', &key.columns)?;
+                            }
+                            if let Some(old) = &message.old {
+                                writer.put_tuple(b'O', &old.columns)?;
+                            }
+                        }
+                        Self::Truncate(message) => writer.put_message(b'T', message)?,
+                        Self::StreamStart(message) => writer.put_message(b'S', message)?,
+                        Self::StreamStop(_) => writer.put_u8(b'E'),
+                        Self::StreamCommit(message) => writer.put_message(b'c', message)?,
+                        Self::StreamAbort(message) => writer.put_message(b'A', message)?,
+                        Self::BeginPrepare(message) => writer.put_message(b'b', message)?,
+                        Self::Prepare(message) => writer.put_message(b'P', message)?,
+                        Self::CommitPrepared(message) => writer.put_message(b'K', message)?,
+                        Self::RollbackPrepared(message) => writer.put_message(b'r', message)?,
+                        Self::StreamPrepare(message) => writer.put_message(b'p', message)?,
+                        Self::Unknown { tag, payload } => {
+                            writer.put_u8(*tag);
+                            writer.buf.extend_from_slice(payload);
+                        }
+                    }
+
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_encode_message!(ReplicationMessage<'_>, OwnedReplicationMessage);
+
+/// A message body that doesn't borrow from the buffer, shared by both message enums.
+trait EncodeBody {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError>;
+}
+
+struct Writer<'b> {
+    buf: &'b mut Vec<u8>,
+}
+
+impl Writer<'_> {
+    /// Appends `message` to `buf`, leaving `buf` as it was if it fails.
+    fn write(buf: &mut Vec<u8>, message: &impl EncodeMessage) -> Result<(), EncodeError> {
+        let len = buf.len();
+        let result = message.encode_message(&mut Writer { buf });
+        if result.is_err() {
+            buf.truncate(len);
+        }
+
+        result
+    }
+
+    fn put_message(&mut self, tag: u8, message: &impl EncodeBody) -> Result<(), EncodeError> {
+        self.put_u8(tag);
+        message.encode_body(self)
+    }
+
+    fn put_u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    fn put_i16(&mut self, value: i16) {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn put_i32(&mut self, value: i32) {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn put_i64(&mut self, value: i64) {
+        self.buf.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn put_lsn(&mut self, lsn: Lsn) {
+        self.buf.extend_from_slice(&lsn.0.to_be_bytes());
+    }
+
+    fn put_timestamp(&mut self, timestamp: DateTime<Utc>) {
+        self.put_i64(timestamp::pg_timestamp_to_micros(timestamp));
+    }
+
+    fn put_bool(&mut self, value: bool) {
+        self.put_u8(u8::from(value));
+    }
+
+    fn put_xid(&mut self, xid: Xid) {
+        self.buf.extend_from_slice(&xid.0.to_be_bytes());
+    }
+
+    fn put_streamed_xid(&mut self, transaction_id: Option<Xid>) {
+        if let Some(transaction_id) = transaction_id {
+            self.put_xid(transaction_id);
+        }
+    }
+
+    /// Writes a NUL-terminated string.
+    fn put_string(&mut self, value: &str) {
+        self.buf.extend_from_slice(value.as_bytes());
+        self.put_u8(0);
+    }
+
+    /// Writes an Int16 number of columns.
+    fn put_columns_count(&mut self, count: usize) -> Result<(), EncodeError> {
+        let count = i16::try_from(count).map_err(|_| EncodeError::TooManyColumns { count })?;
+        self.put_i16(count);
+
+        Ok(())
+    }
+
+    /// Writes an Int32 length followed by the bytes.
+    fn put_length_prefixed(&mut self, bytes: &[u8]) -> Result<(), EncodeError> {
+        let length = i32::try_from(bytes.len()).map_err(|_| EncodeError::TooLong {
+            length: bytes.len(),
+        })?;
+        self.put_i32(length);
+        self.buf.extend_from_slice(bytes);
+
+        Ok(())
+    }
+
+    fn put_generic(
+        &mut self,
+        transaction_id: Option<Xid>,
+        is_transactional: bool,
+        lsn: Lsn,
+        prefix: &str,
+        content: &[u8],
+    ) -> Result<(), EncodeError> {
+        self.put_streamed_xid(transaction_id);
+        self.put_bool(is_transactional);
+        self.put_lsn(lsn);
+        self.put_string(prefix);
+        self.put_length_prefixed(content)
+    }
+
+    fn put_tuple(&mut self, tag: u8, columns: &[impl EncodeColumn]) -> Result<(), EncodeError> {
+        self.put_u8(tag);
+        self.put_columns_count(columns.len())?;
+        for column in columns {
+            match column.value() {
+                ColumnValue::Null => self.put_u8(b'n'),
+                ColumnValue::UnchangedToast => self.put_u8(b'u'),
+                ColumnValue::Text(value) => {
+                    self.put_u8(b't');
+                    self.put_length_prefixed(value.as_bytes())?;
+                }
+                ColumnValue::Binary(value) => {
+                    self.put_u8(b'b');
+                    self.put_length_prefixed(value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The value of a tuple column, borrowed from either kind of tuple.
+enum ColumnValue<'v> {
+    Null,
+    UnchangedToast,
+    Text(&'v str),
+    Binary(&'v [u8]),
+}
+
+/// A tuple column, shared by both tuple types.
+trait EncodeColumn {
+    fn value(&self) -> ColumnValue<'_>;
+}
+
+impl EncodeColumn for TupleDataColumn<'_> {
+    fn value(&self) -> ColumnValue<'_> {
+        match &self.kind {
+            TupleDataKind::Null => ColumnValue::Null,
+            TupleDataKind::UnchangedToast => ColumnValue::UnchangedToast,
+            TupleDataKind::Text(value) => ColumnValue::Text(value),
+            TupleDataKind::Binary(value) => ColumnValue::Binary(value),
+        }
+    }
+}
+
+impl EncodeColumn for OwnedTupleDataColumn {
+    fn value(&self) -> ColumnValue<'_> {
+        match &self.kind {
+            OwnedTupleDataKind::Null => ColumnValue::Null,
+            OwnedTupleDataKind::UnchangedToast => ColumnValue::UnchangedToast,
+            OwnedTupleDataKind::Text(value) => ColumnValue::Text(value),
+            OwnedTupleDataKind::Binary(value) => ColumnValue::Binary(value),
+        }
+    }
+}
+
+impl EncodeBody for BeginMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_lsn(self.final_lsn);
+        writer.put_timestamp(self.timestamp);
+        writer.put_xid(self.transaction_id);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for CommitMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        // Flags, currently unused.
+        writer.put_u8(0);
+        writer.put_lsn(self.lsn);
+        writer.put_lsn(self.final_lsn);
+        writer.put_timestamp(self.timestamp);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for OriginMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_lsn(self.lsn);
+        writer.put_string(&self.name);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for RelationMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_streamed_xid(self.transaction_id);
+        writer.put_i32(self.oid);
+        writer.put_string(self.namespace.as_deref().unwrap_or_default());
+        writer.put_string(&self.name);
+        writer.put_u8(self.replica_identity.to_byte());
+        writer.put_columns_count(self.columns.len())?;
+        for column in &self.columns {
+            writer.put_bool(column.is_part_of_the_key);
+            writer.put_string(&column.name);
+            writer.put_i32(column.oid);
+            writer.put_i32(column.type_modifier);
+        }
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for TypeMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_streamed_xid(self.transaction_id);
+        writer.put_i32(self.oid);
+        writer.put_string(self.namespace.as_deref().unwrap_or_default());
+        writer.put_string(&self.name);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for TruncateMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_streamed_xid(self.transaction_id);
+        let count = self.oids.len();
+        writer.put_i32(i32::try_from(count).map_err(|_| EncodeError::TooManyRelations { count })?);
+        writer.put_u8(self.options().bits());
+        for &oid in &self.oids {
+            writer.put_i32(oid);
+        }
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for StreamStartMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_xid(self.transaction_id.unwrap_or_default());
+        writer.put_bool(self.is_first_segment);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for StreamCommitMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_xid(self.transaction_id);
+        // Flags, currently unused.
+        writer.put_u8(0);
+        writer.put_lsn(self.lsn);
+        writer.put_lsn(self.final_lsn);
+        writer.put_timestamp(self.timestamp);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for StreamAbortMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_xid(self.transaction_id);
+        writer.put_xid(self.subtransaction_id);
+        if let (Some(abort_lsn), Some(abort_timestamp)) = (self.abort_lsn, self.abort_timestamp) {
+            writer.put_lsn(abort_lsn);
+            writer.put_timestamp(abort_timestamp);
+        }
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for BeginPrepareMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        writer.put_lsn(self.lsn);
+        writer.put_lsn(self.final_lsn);
+        writer.put_timestamp(self.timestamp);
+        writer.put_xid(self.transaction_id);
+        writer.put_string(&self.gid);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for PrepareMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        // Flags, currently unused.
+        writer.put_u8(0);
+        writer.put_lsn(self.lsn);
+        writer.put_lsn(self.final_lsn);
+        writer.put_timestamp(self.timestamp);
+        writer.put_xid(self.transaction_id);
+        writer.put_string(&self.gid);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for CommitPreparedMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        // Flags, currently unused.
+        writer.put_u8(0);
+        writer.put_lsn(self.lsn);
+        writer.put_lsn(self.final_lsn);
+        writer.put_timestamp(self.timestamp);
+        writer.put_xid(self.transaction_id);
+        writer.put_string(&self.gid);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for RollbackPreparedMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        // Flags, currently unused.
+        writer.put_u8(0);
+        writer.put_lsn(self.lsn);
+        writer.put_lsn(self.final_lsn);
+        writer.put_timestamp(self.prepare_timestamp);
+        writer.put_timestamp(self.timestamp);
+        writer.put_xid(self.transaction_id);
+        writer.put_string(&self.gid);
+
+        Ok(())
+    }
+}
+
+impl EncodeBody for StreamPrepareMessage {
+    fn encode_body(&self, writer: &mut Writer) -> Result<(), EncodeError> {
+        // Flags, currently unused.
+        writer.put_u8(0);
+        writer.put_lsn(self.lsn);
+        writer.put_lsn(self.final_lsn);
+        writer.put_timestamp(self.timestamp);
+        writer.put_xid(self.transaction_id);
+        writer.put_string(&self.gid);
+
+        Ok(())
+    }
+}
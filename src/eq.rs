@@ -0,0 +1,54 @@
+//! `PartialEq` for types holding timestamps, compared at the microsecond precision of Postgres.
+//!
+//! [`DateTime`] has nanosecond precision, which Postgres never had, so a timestamp that went
+//! through a conversion keeping sub-microsecond digits would otherwise compare unequal after
+//! being encoded and parsed back.
+
+use chrono::{DateTime, Utc};
+
+use crate::*;
+
+/// Compares timestamps truncated to microsecond precision.
+fn timestamp_eq(a: &DateTime<Utc>, b: &DateTime<Utc>) -> bool {
+    a.timestamp_micros() == b.timestamp_micros()
+}
+
+/// Timestamps are compared truncated to microsecond precision.
+impl PartialEq for StreamAbortMessage {
+    fn eq(&self, other: &Self) -> bool {
+        self.transaction_id == other.transaction_id
+            && self.subtransaction_id == other.subtransaction_id
+            && self.abort_lsn == other.abort_lsn
+            && self.abort_timestamp.map(|t| t.timestamp_micros())
+                == other.abort_timestamp.map(|t| t.timestamp_micros())
+    }
+}
+
+macro_rules! impl_partial_eq {
+    ($($ty:ty { $($field:ident),* ; $($timestamp:ident),* })*) => {
+        $(
+            /// Timestamps are compared truncated to microsecond precision.
+            impl PartialEq for $ty {
+                fn eq(&self, other: &Self) -> bool {
+                    $(self.$field == other.$field &&)*
+                    $(timestamp_eq(&self.$timestamp, &other.$timestamp) &&)*
+                    true
+                }
+            }
+        )*
+    };
+}
+
+impl_partial_eq! {
+    BeginMessage { final_lsn, transaction_id; timestamp }
+    CommitMessage { lsn, final_lsn; timestamp }
+    StreamCommitMessage { transaction_id, lsn, final_lsn; timestamp }
+    BeginPrepareMessage { lsn, final_lsn, transaction_id, gid; timestamp }
+    PrepareMessage { lsn, final_lsn, transaction_id, gid; timestamp }
+    CommitPreparedMessage { lsn, final_lsn, transaction_id, gid; timestamp }
+    RollbackPreparedMessage { lsn, final_lsn, transaction_id, gid; prepare_timestamp, timestamp }
+    StreamPrepareMessage { lsn, final_lsn, transaction_id, gid; timestamp }
+    XLogData<'_> { wal_start, wal_end, payload; clock }
+    PrimaryKeepalive { wal_end, reply_requested; clock }
+    StandbyStatusUpdate { written_lsn, flushed_lsn, applied_lsn, reply_requested; clock }
+}
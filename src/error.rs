@@ -0,0 +1,571 @@
+use core::fmt;
+use core::num::{ParseFloatError, ParseIntError};
+use core::str::Utf8Error;
+
+use crate::Lsn;
+
+/// An error that can occur while parsing a logical replication message.
+///
+/// Offsets are counted in bytes from the start of the parsed buffer.
+///
+/// NOTE: Variants depend on the enabled features (e.g. `Io` with `tokio-codec`), so matches
+/// need a wildcard arm.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseError {
+    /// The buffer ended before the message was complete.
+    UnexpectedEof {
+        /// Offset of the field that could not be read.
+        offset: usize,
+        /// Number of bytes the field needs.
+        needed: usize,
+        /// Number of bytes left in the buffer.
+        remaining: usize,
+    },
+    /// The leading message-type byte is not a known pgoutput message.
+    UnknownMessageType(u8),
+    /// A string field is not valid UTF-8.
+    InvalidUtf8 {
+        /// Offset of the first invalid byte.
+        offset: usize,
+    },
+    /// A [`TupleData`](crate::TupleData) column has an unknown kind byte.
+    InvalidTupleColumnKind {
+        /// Offset of the kind byte.
+        offset: usize,
+        /// The kind byte.
+        kind: u8,
+    },
+    /// A tuple submessage has an unexpected tag (expected `'K'`, `'O'` or `'N'`).
+    InvalidTupleType {
+        /// Offset of the tag byte.
+        offset: usize,
+        /// The tag byte.
+        tag: u8,
+    },
+    /// A relation has an unknown replica identity setting.
+    InvalidReplicaIdentity {
+        /// Offset of the replica identity byte.
+        offset: usize,
+        /// The replica identity byte.
+        byte: u8,
+    },
+    /// A length or count field is negative, or -1 (NULL) where a value is required.
+    InvalidLength {
+        /// Offset of the length field.
+        offset: usize,
+        /// The length value.
+        length: i32,
+    },
+    /// A timestamp is outside of the range supported by [`chrono::DateTime`].
+    InvalidTimestamp {
+        /// Offset of the timestamp field.
+        offset: usize,
+        /// Microseconds since the Postgres epoch.
+        micros: i64,
+    },
+    /// The buffer holds more bytes than the message, which means it was framed incorrectly.
+    TrailingBytes {
+        /// Offset of the first byte after the message.
+        offset: usize,
+        /// Number of bytes after the message.
+        count: usize,
+    },
+    /// The GID of a prepared transaction is longer than [`MAX_GID_LENGTH`](crate::MAX_GID_LENGTH).
+    GidTooLong {
+        /// Offset of the GID.
+        offset: usize,
+        /// Length of the GID in bytes.
+        length: usize,
+    },
+    /// Reading the underlying stream failed.
+    #[cfg(feature = "tokio-codec")]
+    Io(std::io::ErrorKind),
+}
+
+impl ParseError {
+    /// Returns the offset in the buffer where parsing failed.
+    pub fn offset(&self) -> usize {
+        match *self {
+            Self::UnknownMessageType(_) => 0,
+            #[cfg(feature = "tokio-codec")]
+            Self::Io(_) => 0,
+            Self::UnexpectedEof { offset, .. }
+            | Self::InvalidUtf8 { offset }
+            | Self::InvalidTupleColumnKind { offset, .. }
+            | Self::InvalidTupleType { offset, .. }
+            | Self::InvalidReplicaIdentity { offset, .. }
+            | Self::InvalidLength { offset, .. }
+            | Self::InvalidTimestamp { offset, .. }
+            | Self::TrailingBytes { offset, .. }
+            | Self::GidTooLong { offset, .. } => offset,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::UnexpectedEof {
+                offset,
+                needed,
+                remaining,
+            } => write!(
+                f,
+                "unexpected end of buffer at offset {offset}: needed {needed} bytes, {remaining} remaining"
+            ),
+            Self::UnknownMessageType(tag) => {
+                write!(f, "unknown message type {:?} at offset 0", char::from(tag))
+            }
+            Self::InvalidUtf8 { offset } => write!(f, "invalid UTF-8 at offset {offset}"),
+            Self::InvalidTupleColumnKind { offset, kind } => write!(
+                f,
+                "invalid tuple column kind {:?} at offset {offset}",
+                char::from(kind)
+            ),
+            Self::InvalidTupleType { offset, tag } => write!(
+                f,
+                "invalid tuple type {:?} at offset {offset}",
+                char::from(tag)
+            ),
+            Self::InvalidReplicaIdentity { offset, byte } => write!(
+                f,
+                "invalid replica identity {:?} at offset {offset}",
+                char::from(byte)
+            ),
+            Self::InvalidLength { offset, length } => {
+                write!(f, "invalid length {length} at offset {offset}")
+            }
+            Self::InvalidTimestamp { offset, micros } => write!(
+                f,
+                "timestamp {micros} at offset {offset} is out of range"
+            ),
+            Self::TrailingBytes { offset, count } => {
+                write!(f, "{count} trailing bytes after the message at offset {offset}")
+            }
+            Self::GidTooLong { offset, length } => {
+                write!(f, "GID of {length} bytes at offset {offset} is too long")
+            }
+            #[cfg(feature = "tokio-codec")]
+            Self::Io(kind) => write!(f, "I/O error: {kind}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseError {}
+
+#[cfg(feature = "tokio-codec")]
+impl From<std::io::Error> for ParseError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.kind())
+    }
+}
+
+/// An error returned when parsing an [`Lsn`](crate::Lsn) from its `XX/XX` text form fails.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLsnError;
+
+impl fmt::Display for ParseLsnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid LSN, expected `XXXXXXXX/XXXXXXXX` hex notation")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseLsnError {}
+
+/// An error returned when a [`TupleData`](crate::TupleData) doesn't have as many columns as its
+/// [`RelationMessage`](crate::RelationMessage), which means the cached relation is out of date.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MismatchError {
+    /// Number of columns of the relation.
+    pub expected: usize,
+    /// Number of columns of the tuple.
+    pub actual: usize,
+}
+
+impl fmt::Display for MismatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tuple has {} columns, but its relation has {}",
+            self.actual, self.expected
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MismatchError {}
+
+/// An error returned when the GID of a prepared transaction is longer than
+/// [`MAX_GID_LENGTH`](crate::MAX_GID_LENGTH).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GidTooLongError {
+    /// Length of the GID in bytes.
+    pub length: usize,
+}
+
+impl fmt::Display for GidTooLongError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "GID is {} bytes long, but at most {} bytes are allowed",
+            self.length,
+            crate::MAX_GID_LENGTH
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for GidTooLongError {}
+
+/// An error returned by [`ReplicationMessage::encode`](crate::ReplicationMessage::encode) for
+/// a message that doesn't fit the pgoutput wire format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// A tuple or relation has more columns than fit in an Int16.
+    TooManyColumns {
+        /// Number of columns.
+        count: usize,
+    },
+    /// A truncate has more relations than fit in an Int32.
+    TooManyRelations {
+        /// Number of relations.
+        count: usize,
+    },
+    /// A column value or generic message content is longer than fits in an Int32.
+    TooLong {
+        /// Length of the value in bytes.
+        length: usize,
+    },
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TooManyColumns { count } => write!(f, "{count} columns don't fit in an Int16"),
+            Self::TooManyRelations { count } => {
+                write!(f, "{count} relations don't fit in an Int32")
+            }
+            Self::TooLong { length } => {
+                write!(f, "value of {length} bytes doesn't fit in an Int32")
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EncodeError {}
+
+/// An error returned by [`ReplicationMessage::validate`](crate::ReplicationMessage::validate)
+/// for a message whose fields contradict each other, which Postgres never sends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationError {
+    /// A non-transactional generic message has an xid.
+    NonTransactionalXid,
+    /// The `length` of a generic message doesn't match its content.
+    GenericLengthMismatch {
+        /// The `length` field.
+        length: i32,
+        /// Length of the content in bytes.
+        actual: usize,
+    },
+    /// The end LSN of a transaction is before its commit (or prepare) LSN.
+    FinalLsnBeforeLsn {
+        /// The `lsn` field.
+        lsn: Lsn,
+        /// The `final_lsn` field.
+        final_lsn: Lsn,
+    },
+    /// A tuple has more columns than Postgres allows in a row.
+    TooManyColumns {
+        /// Number of columns of the tuple.
+        count: usize,
+    },
+    /// A relation has more columns than Postgres allows in a table.
+    TooManyRelationColumns {
+        /// Number of columns of the relation.
+        count: usize,
+    },
+    /// An update or delete has both a key and an old tuple, while it's sent with one of them at
+    /// most.
+    KeyAndOldTuple,
+    /// The old tuple of an update doesn't have as many columns as the new one.
+    TupleLengthMismatch {
+        /// Number of columns of the old tuple.
+        old: usize,
+        /// Number of columns of the new tuple.
+        new: usize,
+    },
+    /// A stream abort has the abort LSN or the abort timestamp, but not both.
+    IncompleteAbortInfo,
+    /// The GID of a prepared transaction is longer than [`MAX_GID_LENGTH`](crate::MAX_GID_LENGTH).
+    GidTooLong(GidTooLongError),
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NonTransactionalXid => f.write_str("non-transactional message has an xid"),
+            Self::GenericLengthMismatch { length, actual } => write!(
+                f,
+                "generic message length is {length}, but its content is {actual} bytes long"
+            ),
+            Self::FinalLsnBeforeLsn { lsn, final_lsn } => {
+                write!(f, "end LSN {final_lsn} is before LSN {lsn}")
+            }
+            Self::TooManyColumns { count } => write!(f, "tuple has too many columns: {count}"),
+            Self::TooManyRelationColumns { count } => {
+                write!(f, "relation has too many columns: {count}")
+            }
+            Self::KeyAndOldTuple => f.write_str("change has both a key and an old tuple"),
+            Self::TupleLengthMismatch { old, new } => write!(
+                f,
+                "old tuple has {old} columns, but the new tuple has {new}"
+            ),
+            Self::IncompleteAbortInfo => {
+                f.write_str("stream abort has only one of the abort LSN and timestamp")
+            }
+            Self::GidTooLong(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValidationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::GidTooLong(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// An error that can occur while decoding the value of a [`TupleDataColumn`](crate::TupleDataColumn).
+///
+/// NOTE: Variants depend on the enabled features (e.g. `ParseDecimal` with `decimal`), so
+/// matches need a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ValueDecodeError {
+    /// The column is NULL.
+    Null,
+    /// The column is an unchanged TOASTed value, which is not sent.
+    UnchangedToast,
+    /// The binary value has an unexpected length.
+    UnexpectedLength {
+        /// Expected length in bytes.
+        expected: usize,
+        /// Actual length in bytes.
+        actual: usize,
+    },
+    /// The value was sent in a format it can't be decoded from, e.g. a record in text format.
+    WrongFormat {
+        /// Name of the Postgres type.
+        type_name: &'static str,
+    },
+    /// The binary value of a textual type (e.g. `text` or an enum) is not valid UTF-8.
+    Utf8(Utf8Error),
+    /// The text value is not a valid integer.
+    ParseInt(ParseIntError),
+    /// The text value is not a valid floating point number.
+    ParseFloat(ParseFloatError),
+    /// The text value is not a valid date or time.
+    ParseDateTime(chrono::ParseError),
+    /// The text value is not a valid decimal number, or it doesn't fit into [`rust_decimal::Decimal`].
+    #[cfg(feature = "decimal")]
+    ParseDecimal(rust_decimal::Error),
+    /// The value is not valid JSON.
+    #[cfg(feature = "json")]
+    ParseJson(serde_json::Error),
+    /// The text value is not a valid UUID.
+    #[cfg(feature = "uuid")]
+    ParseUuid(uuid::Error),
+    /// The text value is not a valid date or time, as parsed into a [`time`] type.
+    #[cfg(feature = "time")]
+    ParseTime(time::error::Parse),
+    /// The type OID is not a built-in Postgres type, or has no decoder in a
+    /// [`TypeRegistry`](crate::TypeRegistry).
+    UnknownType(i32),
+    /// Decoding the value with [`postgres_types::FromSql`] or [`std::str::FromStr`] failed.
+    #[cfg(feature = "postgres-types")]
+    Decode(alloc::boxed::Box<dyn std::error::Error + Send + Sync>),
+    /// The value is not a valid representation of the Postgres type.
+    InvalidValue {
+        /// Name of the Postgres type.
+        type_name: &'static str,
+    },
+}
+
+impl fmt::Display for ValueDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Null => f.write_str("value is NULL"),
+            Self::UnchangedToast => f.write_str("value is an unchanged TOASTed value"),
+            Self::UnexpectedLength { expected, actual } => write!(
+                f,
+                "unexpected binary value length: expected {expected} bytes, got {actual}"
+            ),
+            Self::WrongFormat { type_name } => {
+                write!(
+                    f,
+                    "{type_name} value sent in a format that can't be decoded"
+                )
+            }
+            Self::Utf8(err) => write!(f, "invalid UTF-8 value: {err}"),
+            Self::ParseInt(err) => write!(f, "invalid integer value: {err}"),
+            Self::ParseFloat(err) => write!(f, "invalid floating point value: {err}"),
+            Self::ParseDateTime(err) => write!(f, "invalid date/time value: {err}"),
+            #[cfg(feature = "decimal")]
+            Self::ParseDecimal(err) => write!(f, "invalid decimal value: {err}"),
+            #[cfg(feature = "json")]
+            Self::ParseJson(err) => write!(f, "invalid json value: {err}"),
+            #[cfg(feature = "uuid")]
+            Self::ParseUuid(err) => write!(f, "invalid uuid value: {err}"),
+            #[cfg(feature = "time")]
+            Self::ParseTime(err) => write!(f, "invalid date/time value: {err}"),
+            Self::UnknownType(oid) => write!(f, "unknown type oid {oid}"),
+            #[cfg(feature = "postgres-types")]
+            Self::Decode(err) => write!(f, "invalid value: {err}"),
+            Self::InvalidValue { type_name } => write!(f, "invalid {type_name} value"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ValueDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Utf8(err) => Some(err),
+            Self::ParseInt(err) => Some(err),
+            Self::ParseFloat(err) => Some(err),
+            Self::ParseDateTime(err) => Some(err),
+            #[cfg(feature = "decimal")]
+            Self::ParseDecimal(err) => Some(err),
+            #[cfg(feature = "json")]
+            Self::ParseJson(err) => Some(err),
+            #[cfg(feature = "uuid")]
+            Self::ParseUuid(err) => Some(err),
+            #[cfg(feature = "time")]
+            Self::ParseTime(err) => Some(err),
+            #[cfg(feature = "postgres-types")]
+            Self::Decode(err) => Some(&**err),
+            _ => None,
+        }
+    }
+}
+
+/// An error that can occur while converting inserts to an Arrow
+/// [`RecordBatch`](arrow_array::RecordBatch).
+#[cfg(feature = "arrow")]
+#[derive(Debug)]
+pub enum RecordBatchError {
+    /// An insert is for a different relation than the batch.
+    WrongRelation {
+        /// OID of the relation of the batch.
+        expected: i32,
+        /// OID of the relation of the insert.
+        actual: i32,
+    },
+    /// A tuple doesn't have as many columns as the relation.
+    Mismatch(MismatchError),
+    /// A value couldn't be decoded as the type of its column.
+    Decode {
+        /// Name of the column.
+        column: alloc::string::String,
+        /// The decoding error.
+        error: ValueDecodeError,
+    },
+    /// Arrow rejected the batch, e.g. because a non-nullable column holds a NULL.
+    Arrow(arrow_schema::ArrowError),
+}
+
+#[cfg(feature = "arrow")]
+impl fmt::Display for RecordBatchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::WrongRelation { expected, actual } => write!(
+                f,
+                "insert is for relation {actual}, but the batch is for relation {expected}"
+            ),
+            Self::Mismatch(err) => err.fmt(f),
+            Self::Decode { column, error } => write!(f, "column {column:?}: {error}"),
+            Self::Arrow(err) => write!(f, "arrow error: {err}"),
+        }
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl std::error::Error for RecordBatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::WrongRelation { .. } => None,
+            Self::Mismatch(err) => Some(err),
+            Self::Decode { error, .. } => Some(error),
+            Self::Arrow(err) => Some(err),
+        }
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<MismatchError> for RecordBatchError {
+    fn from(err: MismatchError) -> Self {
+        Self::Mismatch(err)
+    }
+}
+
+#[cfg(feature = "arrow")]
+impl From<arrow_schema::ArrowError> for RecordBatchError {
+    fn from(err: arrow_schema::ArrowError) -> Self {
+        Self::Arrow(err)
+    }
+}
+
+/// An error that can occur while decoding a stream with
+/// [`ReplicationStream`](crate::ReplicationStream).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReplicationStreamError {
+    /// A message couldn't be parsed.
+    Parse(ParseError),
+    /// A change is for a relation whose [`RelationMessage`](crate::RelationMessage) wasn't seen.
+    UnknownRelation {
+        /// OID of the relation.
+        oid: i32,
+    },
+    /// A tuple doesn't have as many columns as its relation.
+    Mismatch(MismatchError),
+}
+
+impl fmt::Display for ReplicationStreamError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Parse(err) => err.fmt(f),
+            Self::UnknownRelation { oid } => write!(f, "change for unknown relation {oid}"),
+            Self::Mismatch(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReplicationStreamError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Parse(err) => Some(err),
+            Self::UnknownRelation { .. } => None,
+            Self::Mismatch(err) => Some(err),
+        }
+    }
+}
+
+impl From<ParseError> for ReplicationStreamError {
+    fn from(err: ParseError) -> Self {
+        Self::Parse(err)
+    }
+}
+
+impl From<MismatchError> for ReplicationStreamError {
+    fn from(err: MismatchError) -> Self {
+        Self::Mismatch(err)
+    }
+}
@@ -0,0 +1,38 @@
+use alloc::boxed::Box;
+use core::str::FromStr;
+use std::error::Error;
+
+use postgres_types::{FromSql, Type, WrongType};
+
+use crate::{TupleDataColumn, TupleDataKind, ValueDecodeError};
+
+impl<'a> TupleDataColumn<'a> {
+    /// Decodes the value of the column as `T`, given the OID of its type.
+    ///
+    /// Binary values are decoded with [`FromSql`], text values with [`FromStr`]. The OID is
+    /// usually taken from the [`RelationMessageColumn`](crate::RelationMessageColumn).
+    ///
+    /// Returns [`ValueDecodeError::Null`] for NULL values and
+    /// [`ValueDecodeError::UnchangedToast`] for unchanged TOASTed values.
+    pub fn decode<T>(&self, oid: i32) -> Result<T, ValueDecodeError>
+    where
+        T: FromSql<'a> + FromStr,
+        T::Err: Into<Box<dyn Error + Send + Sync>>,
+    {
+        match &self.kind {
+            TupleDataKind::Null => Err(ValueDecodeError::Null),
+            TupleDataKind::UnchangedToast => Err(ValueDecodeError::UnchangedToast),
+            TupleDataKind::Text(text) => text
+                .parse()
+                .map_err(|err: T::Err| ValueDecodeError::Decode(err.into())),
+            TupleDataKind::Binary(bytes) => {
+                // NOTE: OIDs are unsigned in Postgres, the messages carry them as `i32`.
+                let ty = Type::from_oid(oid as u32).ok_or(ValueDecodeError::UnknownType(oid))?;
+                if !T::accepts(&ty) {
+                    return Err(ValueDecodeError::Decode(Box::new(WrongType::new::<T>(ty))));
+                }
+                T::from_sql(&ty, bytes).map_err(ValueDecodeError::Decode)
+            }
+        }
+    }
+}
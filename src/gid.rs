@@ -0,0 +1,41 @@
+use crate::error::GidTooLongError;
+use crate::{
+    BeginPrepareMessage, CommitPreparedMessage, PrepareMessage, RollbackPreparedMessage,
+    StreamPrepareMessage,
+};
+
+/// Maximum length in bytes of the GID of a prepared transaction.
+///
+/// Postgres stores GIDs in a buffer of `GIDSIZE` (200) bytes, including the NUL terminator,
+/// and rejects longer ones in `PREPARE TRANSACTION`.
+pub const MAX_GID_LENGTH: usize = 199;
+
+pub(crate) fn validate_gid(gid: &str) -> Result<&str, GidTooLongError> {
+    if gid.len() > MAX_GID_LENGTH {
+        return Err(GidTooLongError { length: gid.len() });
+    }
+
+    Ok(gid)
+}
+
+macro_rules! impl_gid {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl $ty {
+                /// Returns the GID of the prepared transaction, checking that it's no longer than
+                /// [`MAX_GID_LENGTH`].
+                pub fn gid(&self) -> Result<&str, GidTooLongError> {
+                    validate_gid(&self.gid)
+                }
+            }
+        )*
+    };
+}
+
+impl_gid! {
+    BeginPrepareMessage,
+    PrepareMessage,
+    CommitPreparedMessage,
+    RollbackPreparedMessage,
+    StreamPrepareMessage,
+}
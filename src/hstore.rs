@@ -0,0 +1,114 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::iter::Peekable;
+use core::str::Chars;
+
+use crate::array::BinaryReader;
+use crate::{TupleDataColumn, TupleDataKind, ValueDecodeError};
+
+impl TupleDataColumn<'_> {
+    /// Decodes an `hstore` value into its pairs, in the order they were sent.
+    ///
+    /// NULL values are returned as `None`. Text values are expected as Postgres prints them,
+    /// e.g. `"a"=>"b", "c"=>NULL`.
+    pub fn as_hstore(&self) -> Result<Vec<(String, Option<String>)>, ValueDecodeError> {
+        match &self.kind {
+            TupleDataKind::Null => Err(ValueDecodeError::Null),
+            TupleDataKind::UnchangedToast => Err(ValueDecodeError::UnchangedToast),
+            TupleDataKind::Text(text) => parse_text(text).ok_or_else(invalid),
+            TupleDataKind::Binary(bytes) => parse_binary(bytes),
+        }
+    }
+}
+
+fn invalid() -> ValueDecodeError {
+    ValueDecodeError::InvalidValue {
+        type_name: "hstore",
+    }
+}
+
+fn parse_text(text: &str) -> Option<Vec<(String, Option<String>)>> {
+    let mut pairs = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        skip_whitespace(&mut chars);
+        if chars.peek().is_none() {
+            break;
+        }
+
+        let (key, _) = read_token(&mut chars)?;
+        skip_whitespace(&mut chars);
+        if (chars.next(), chars.next()) != (Some('='), Some('>')) {
+            return None;
+        }
+        skip_whitespace(&mut chars);
+        let (value, quoted) = read_token(&mut chars)?;
+        let is_null = !quoted && value.eq_ignore_ascii_case("NULL");
+        pairs.push((key, (!is_null).then_some(value)));
+
+        skip_whitespace(&mut chars);
+        match chars.next() {
+            Some(',') => continue,
+            None => break,
+            Some(_) => return None,
+        }
+    }
+
+    Some(pairs)
+}
+
+fn skip_whitespace(chars: &mut Peekable<Chars<'_>>) {
+    while chars.next_if(|c| c.is_whitespace()).is_some() {}
+}
+
+/// Reads a key or value, returning it unescaped together with whether it was quoted.
+fn read_token(chars: &mut Peekable<Chars<'_>>) -> Option<(String, bool)> {
+    let mut token = String::new();
+    if chars.next_if_eq(&'"').is_some() {
+        loop {
+            match chars.next()? {
+                '"' => return Some((token, true)),
+                '\\' => token.push(chars.next()?),
+                c => token.push(c),
+            }
+        }
+    }
+
+    while let Some(c) = chars.next_if(|&c| !c.is_whitespace() && c != '=' && c != ',') {
+        match c {
+            '\\' => token.push(chars.next()?),
+            '"' => return None,
+            c => token.push(c),
+        }
+    }
+
+    (!token.is_empty()).then_some((token, false))
+}
+
+/// Decodes the binary format: the number of pairs, followed by the length-prefixed key and
+/// value of every pair, where a value length of `-1` means NULL.
+fn parse_binary(bytes: &[u8]) -> Result<Vec<(String, Option<String>)>, ValueDecodeError> {
+    let mut reader = BinaryReader::new(bytes, "hstore");
+    let count = usize::try_from(reader.read_i32()?).map_err(|_| invalid())?;
+
+    let pairs = (0..count)
+        .map(|_| {
+            let key = reader.read_element()?.ok_or_else(invalid)?;
+            let value = reader.read_element()?;
+
+            Ok((utf8(key)?, value.map(utf8).transpose()?))
+        })
+        .collect::<Result<_, ValueDecodeError>>()?;
+    if !reader.bytes.is_empty() {
+        return Err(invalid());
+    }
+
+    Ok(pairs)
+}
+
+fn utf8(bytes: &[u8]) -> Result<String, ValueDecodeError> {
+    core::str::from_utf8(bytes)
+        .map(String::from)
+        .map_err(ValueDecodeError::Utf8)
+}
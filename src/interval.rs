@@ -0,0 +1,117 @@
+use crate::value::{fixed, FromValue};
+use crate::{TupleDataColumn, ValueDecodeError};
+
+const MICROS_PER_SECOND: i64 = 1_000_000;
+
+/// A Postgres `interval` value.
+///
+/// Months, days and microseconds are kept separate, as Postgres does, because their lengths
+/// depend on the calendar (a month isn't always 30 days and a day isn't always 24 hours across
+/// DST changes).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Interval {
+    /// Number of months (a year is 12 months).
+    pub months: i32,
+    /// Number of days.
+    pub days: i32,
+    /// Number of microseconds.
+    pub micros: i64,
+}
+
+impl TupleDataColumn<'_> {
+    /// Decodes an `interval` value.
+    ///
+    /// Text values are expected in the default `postgres` interval style
+    /// (e.g. `1 year 2 mons 3 days 04:05:06.789`).
+    pub fn as_interval(&self) -> Result<Interval, ValueDecodeError> {
+        self.value()
+    }
+}
+
+impl FromValue for Interval {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        parse_interval(text).ok_or(ValueDecodeError::InvalidValue {
+            type_name: "interval",
+        })
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        let bytes: [u8; 16] = fixed(bytes)?;
+        let (micros, rest) = bytes.split_at(8);
+        let (days, months) = rest.split_at(4);
+
+        Ok(Self {
+            months: i32::from_be_bytes(months.try_into().unwrap()),
+            days: i32::from_be_bytes(days.try_into().unwrap()),
+            micros: i64::from_be_bytes(micros.try_into().unwrap()),
+        })
+    }
+}
+
+fn parse_interval(text: &str) -> Option<Interval> {
+    let mut interval = Interval::default();
+    let mut tokens = text.split_ascii_whitespace();
+
+    while let Some(token) = tokens.next() {
+        if token.contains(':') {
+            interval.micros = interval.micros.checked_add(parse_time(token)?)?;
+            continue;
+        }
+
+        let amount: i32 = token.parse().ok()?;
+        match tokens.next()? {
+            "year" | "years" => {
+                interval.months = interval.months.checked_add(amount.checked_mul(12)?)?;
+            }
+            "mon" | "mons" => interval.months = interval.months.checked_add(amount)?,
+            "day" | "days" => interval.days = interval.days.checked_add(amount)?,
+            _ => return None,
+        }
+    }
+
+    Some(interval)
+}
+
+/// Parses `[+-]hh:mm:ss[.ffffff]` into microseconds.
+fn parse_time(token: &str) -> Option<i64> {
+    let (negative, token) = match token.as_bytes().first()? {
+        b'-' => (true, &token[1..]),
+        b'+' => (false, &token[1..]),
+        _ => (false, token),
+    };
+
+    let mut parts = token.splitn(3, ':');
+    let hours: i64 = parse_digits(parts.next()?)?;
+    let minutes: i64 = parse_digits(parts.next()?)?;
+    let seconds = parts.next()?;
+    let (seconds, fraction) = match seconds.split_once('.') {
+        Some((seconds, fraction)) => (seconds, Some(fraction)),
+        None => (seconds, None),
+    };
+    let seconds: i64 = parse_digits(seconds)?;
+
+    let mut micros = hours
+        .checked_mul(60)?
+        .checked_add(minutes)?
+        .checked_mul(60)?
+        .checked_add(seconds)?
+        .checked_mul(MICROS_PER_SECOND)?;
+
+    if let Some(fraction) = fraction {
+        if fraction.is_empty() || fraction.len() > 6 {
+            return None;
+        }
+        let digits: i64 = parse_digits(fraction)?;
+        micros = micros.checked_add(digits * 10_i64.pow(6 - fraction.len() as u32))?;
+    }
+
+    Some(if negative { -micros } else { micros })
+}
+
+fn parse_digits(text: &str) -> Option<i64> {
+    if text.is_empty() || !text.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    text.parse().ok()
+}
@@ -0,0 +1,53 @@
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use ipnetwork::{IpNetwork, Ipv4Network, Ipv6Network};
+
+use crate::value::FromValue;
+use crate::{TupleDataColumn, ValueDecodeError};
+
+/// Address family of IPv4 in the binary format, Postgres' `PGSQL_AF_INET`.
+const AF_INET: u8 = 2;
+/// Address family of IPv6 in the binary format, `PGSQL_AF_INET + 1`.
+const AF_INET6: u8 = 3;
+
+const INVALID: ValueDecodeError = ValueDecodeError::InvalidValue { type_name: "inet" };
+
+impl TupleDataColumn<'_> {
+    /// Decodes an `inet` or `cidr` value.
+    ///
+    /// Text values are expected as an address with an optional prefix length (e.g.
+    /// `192.168.0.1/24` or `2001:db8::/32`); without one, the prefix covers the whole address.
+    pub fn as_ip_network(&self) -> Result<IpNetwork, ValueDecodeError> {
+        self.value()
+    }
+}
+
+impl FromValue for IpNetwork {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        text.parse().map_err(|_| INVALID)
+    }
+
+    /// Binary values are the address family, the prefix length, whether the value is a `cidr`,
+    /// the address length and the address, in this order.
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        let [family, prefix, _is_cidr, length, address @ ..] = bytes else {
+            return Err(INVALID);
+        };
+        if usize::from(*length) != address.len() {
+            return Err(INVALID);
+        }
+
+        let network = match (*family, address) {
+            (AF_INET, &[a, b, c, d]) => {
+                Ipv4Network::new(Ipv4Addr::new(a, b, c, d), *prefix).map(IpNetwork::V4)
+            }
+            (AF_INET6, address) => {
+                let address: [u8; 16] = address.try_into().map_err(|_| INVALID)?;
+                Ipv6Network::new(Ipv6Addr::from(address), *prefix).map(IpNetwork::V6)
+            }
+            _ => return Err(INVALID),
+        };
+
+        network.map_err(|_| INVALID)
+    }
+}
@@ -0,0 +1,90 @@
+use crate::{ParseError, ParseOptions, ProtocolVersion, ReplicationMessage};
+
+/// An iterator over logical replication messages concatenated in a single buffer.
+///
+/// Iteration stops at the end of the buffer. If the tail of the buffer doesn't hold a complete
+/// message, the error is yielded and iteration stops as well. Offsets of errors are counted
+/// from the start of the failing message, which is at [`MessageIter::position`].
+#[derive(Debug, Clone)]
+pub struct MessageIter<'a> {
+    buf: &'a [u8],
+    pos: usize,
+    options: ParseOptions,
+    in_stream: bool,
+    failed: bool,
+}
+
+impl<'a> MessageIter<'a> {
+    /// Creates an iterator over messages sent with protocol version 1.
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self::with_version(buf, ProtocolVersion::V1)
+    }
+
+    /// Creates an iterator over messages sent with the given protocol `version`.
+    ///
+    /// Unlike [`ReplicationMessage::parse_with_version`], the leading xid is only read for
+    /// messages between `StreamStart` and `StreamStop`, matching what Postgres sends.
+    pub fn with_version(buf: &'a [u8], version: ProtocolVersion) -> Self {
+        Self::with_options(
+            buf,
+            ParseOptions {
+                version,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Creates an iterator parsing messages with the given [`ParseOptions`].
+    ///
+    /// NOTE: [`ReplicationMessage::Unknown`] consumes the rest of the buffer, so in tolerant mode
+    /// an unknown message is always the last one.
+    pub fn with_options(buf: &'a [u8], options: ParseOptions) -> Self {
+        Self {
+            buf,
+            pos: 0,
+            options,
+            in_stream: false,
+            failed: false,
+        }
+    }
+
+    /// Returns the offset of the next message in the buffer.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+
+    /// Returns the part of the buffer that hasn't been parsed yet.
+    pub fn remaining(&self) -> &'a [u8] {
+        &self.buf[self.pos..]
+    }
+}
+
+impl<'a> Iterator for MessageIter<'a> {
+    type Item = Result<ReplicationMessage<'a>, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed || self.pos == self.buf.len() {
+            return None;
+        }
+
+        match ReplicationMessage::parse_prefix_in_stream(
+            self.remaining(),
+            self.options,
+            self.in_stream,
+        ) {
+            Ok((message, consumed)) => {
+                self.pos += consumed;
+                match message {
+                    ReplicationMessage::StreamStart(_) => self.in_stream = true,
+                    ReplicationMessage::StreamStop(_) => self.in_stream = false,
+                    _ => {}
+                }
+                Some(Ok(message))
+            }
+            Err(err) => {
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use serde_json::Value;
+
+use crate::value::FromValue;
+use crate::{TupleDataColumn, ValueDecodeError};
+
+/// Version header of the binary `jsonb` format.
+const JSONB_VERSION: u8 = 1;
+
+impl TupleDataColumn<'_> {
+    /// Decodes a `json` or `jsonb` value.
+    ///
+    /// The version header of binary `jsonb` values is stripped, as it can't start valid JSON.
+    pub fn as_json(&self) -> Result<Value, ValueDecodeError> {
+        self.value()
+    }
+}
+
+impl FromValue for Value {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        serde_json::from_str(text).map_err(ValueDecodeError::ParseJson)
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        let json = match bytes.split_first() {
+            Some((&JSONB_VERSION, json)) => json,
+            _ => bytes,
+        };
+
+        serde_json::from_slice(json).map_err(ValueDecodeError::ParseJson)
+    }
+}
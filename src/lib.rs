@@ -1,6 +1,164 @@
+#![no_std]
+
+extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ptr;
+
 use chrono::{DateTime, Utc};
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+mod array;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "bincode")]
+mod bincode;
+mod builder;
+mod byte_reader;
+mod bytea;
+mod change_event;
+#[cfg(feature = "tokio-codec")]
+mod codec;
+mod copy_data;
+#[cfg(feature = "debezium")]
+mod debezium;
+mod debug;
+#[cfg(feature = "decimal")]
+mod decimal;
+mod display;
+mod encode;
+mod eq;
+mod error;
+#[cfg(feature = "postgres-types")]
+mod from_sql;
+mod gid;
+mod hstore;
+mod interval;
+#[cfg(feature = "ipnetwork")]
+mod ipnetwork;
+mod iter;
+#[cfg(feature = "json")]
+mod json;
+mod lsn;
+mod macaddr;
+mod message_type;
+mod options;
+mod owned;
+mod parse;
+pub mod prelude;
+mod progress;
+mod range;
+mod record;
+mod relation_cache;
+mod replication_stream;
+mod schema;
+#[cfg(feature = "serde")]
+mod serde_base64;
+#[cfg(feature = "serde")]
+mod serde_tagged;
+mod stats;
+mod stream_reassembler;
+#[cfg(feature = "time")]
+mod time;
+mod timestamp;
+mod type_name;
+mod type_registry;
+#[cfg(feature = "uuid")]
+mod uuid;
+mod validate;
+mod value;
+mod version;
+mod visitor;
+mod xid;
+
+pub use array::PgArray;
+#[cfg(feature = "arrow")]
+pub use arrow::inserts_to_record_batch;
+pub use builder::{
+    DeleteMessageBuilder, InsertMessageBuilder, RelationMessageBuilder, TupleDataBuilder,
+    UpdateMessageBuilder,
+};
+pub use byte_reader::ByteReader;
+pub use change_event::{ChangeEvent, Op, TruncateEvent};
+#[cfg(feature = "tokio-codec")]
+pub use codec::ReplicationDecoder;
+pub use copy_data::{CopyData, PrimaryKeepalive, StandbyStatusUpdate, XLogData};
+#[cfg(feature = "arrow")]
+pub use error::RecordBatchError;
+pub use error::{
+    EncodeError, GidTooLongError, MismatchError, ParseError, ParseLsnError, ReplicationStreamError,
+    ValidationError, ValueDecodeError,
+};
+pub use gid::MAX_GID_LENGTH;
+pub use interval::Interval;
+pub use iter::MessageIter;
+pub use lsn::Lsn;
+pub use macaddr::MacAddr;
+pub use message_type::MessageType;
+pub use options::ParseOptions;
+pub use owned::{
+    OwnedDeleteMessage, OwnedGenericMessage, OwnedInsertMessage, OwnedReplicationMessage,
+    OwnedTupleData, OwnedTupleDataColumn, OwnedTupleDataKind, OwnedUpdateMessage,
+};
+pub use progress::ReplicationProgress;
+pub use range::PgRange;
+pub use record::RecordField;
+pub use relation_cache::{NamedRow, RelationCache};
+pub use replication_stream::{NamedChangeEvent, OwnedNamedRow, ReplicationStream};
+pub use schema::ColumnSchema;
+pub use stats::{ChangeStats, RelationStats};
+pub use stream_reassembler::StreamReassembler;
+pub use timestamp::{pg_timestamp_from_micros, pg_timestamp_to_micros};
+pub use type_name::{
+    builtin_type_name, BOOL_OID, BPCHAR_OID, BYTEA_OID, DATE_OID, FLOAT4_OID, FLOAT8_OID, INT2_OID,
+    INT4_OID, INT8_OID, NAME_OID, OID_OID, TEXT_OID, TIMESTAMPTZ_OID, TIMESTAMP_OID, TIME_OID,
+    VARCHAR_OID,
+};
+pub use type_registry::TypeRegistry;
+pub use value::FromValue;
+pub use version::ProtocolVersion;
+pub use visitor::MessageVisitor;
+pub use xid::Xid;
+
+// Messages and decoders can be moved across threads and shared between them; this fails to
+// compile if one stops being `Send + Sync`.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    assert_send_sync::<ReplicationMessage<'_>>();
+    assert_send_sync::<OwnedReplicationMessage>();
+    assert_send_sync::<OwnedTupleData>();
+    assert_send_sync::<ChangeEvent<'_>>();
+    assert_send_sync::<TruncateEvent>();
+    assert_send_sync::<NamedChangeEvent>();
+    assert_send_sync::<ParseError>();
+    assert_send_sync::<EncodeError>();
+    assert_send_sync::<ValueDecodeError>();
+    assert_send_sync::<ValidationError>();
+    assert_send_sync::<ReplicationStreamError>();
+    assert_send_sync::<RelationCache>();
+    assert_send_sync::<StreamReassembler>();
+    assert_send_sync::<ReplicationStream>();
+    assert_send_sync::<ReplicationProgress>();
+    assert_send_sync::<ChangeStats>();
+    assert_send_sync::<MessageIter<'_>>();
+    assert_send_sync::<CopyData<'_>>();
+    assert_send_sync::<TypeRegistry<i64>>();
+    #[cfg(feature = "tokio-codec")]
+    assert_send_sync::<ReplicationDecoder>();
+};
+
 /// A logical replication message.
+///
+/// NOTE: Future Postgres versions may add new message types, so the enum is non-exhaustive.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
 pub enum ReplicationMessage<'a> {
     Begin(BeginMessage),
     Generic(GenericMessage<'a>),
@@ -21,57 +179,138 @@ pub enum ReplicationMessage<'a> {
     CommitPrepared(CommitPreparedMessage),
     RollbackPrepared(RollbackPreparedMessage),
     StreamPrepare(StreamPrepareMessage),
+    /// A message of a type unknown to this crate, only produced by
+    /// [`ReplicationMessage::parse_tolerant`].
+    Unknown {
+        /// The message-type byte.
+        tag: u8,
+        /// The rest of the buffer after the tag.
+        payload: &'a [u8],
+    },
+}
+
+impl ReplicationMessage<'_> {
+    /// The LSN of the message most relevant for progress tracking, if it has one.
+    ///
+    /// This is the LSN of the commit, prepare, rollback or abort for the messages ending a
+    /// transaction, the final LSN of the transaction for `Begin`, and the LSN of the message
+    /// for `Generic`. `Origin` carries an LSN of the origin server, so it returns `None`, as do
+    /// all other messages without an LSN.
+    pub fn lsn(&self) -> Option<Lsn> {
+        match self {
+            Self::Begin(message) => Some(message.final_lsn),
+            Self::Generic(message) => Some(message.lsn),
+            Self::Commit(message) => Some(message.lsn),
+            Self::StreamCommit(message) => Some(message.lsn),
+            Self::StreamAbort(message) => message.abort_lsn,
+            Self::BeginPrepare(message) => Some(message.lsn),
+            Self::Prepare(message) => Some(message.lsn),
+            Self::CommitPrepared(message) => Some(message.lsn),
+            Self::RollbackPrepared(message) => Some(message.lsn),
+            Self::StreamPrepare(message) => Some(message.lsn),
+            Self::Origin(_)
+            | Self::Relation(_)
+            | Self::Type(_)
+            | Self::Insert(_)
+            | Self::Update(_)
+            | Self::Delete(_)
+            | Self::Truncate(_)
+            | Self::StreamStart(_)
+            | Self::StreamStop(_)
+            | Self::Unknown { .. } => None,
+        }
+    }
+
+    /// The xid of the transaction the message belongs to, if it carries one.
+    ///
+    /// Inside stream blocks, changes carry the xid of their subtransaction, which may differ
+    /// from the xid of the enclosing `StreamStart`. `Commit` doesn't carry an xid at all; it
+    /// belongs to the transaction of the preceding `Begin`.
+    pub fn transaction_id(&self) -> Option<Xid> {
+        match self {
+            Self::Begin(message) => Some(message.transaction_id),
+            Self::Generic(message) => message.transaction_id,
+            Self::Relation(message) => message.transaction_id,
+            Self::Type(message) => message.transaction_id,
+            Self::Insert(message) => message.transaction_id,
+            Self::Update(message) => message.transaction_id,
+            Self::Delete(message) => message.transaction_id,
+            Self::Truncate(message) => message.transaction_id,
+            Self::StreamStart(message) => message.transaction_id,
+            Self::StreamCommit(message) => Some(message.transaction_id),
+            Self::StreamAbort(message) => Some(message.transaction_id),
+            Self::BeginPrepare(message) => Some(message.transaction_id),
+            Self::Prepare(message) => Some(message.transaction_id),
+            Self::CommitPrepared(message) => Some(message.transaction_id),
+            Self::RollbackPrepared(message) => Some(message.transaction_id),
+            Self::StreamPrepare(message) => Some(message.transaction_id),
+            Self::Commit(_) | Self::Origin(_) | Self::StreamStop(_) | Self::Unknown { .. } => None,
+        }
+    }
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeginMessage {
     /// The final LSN of the transaction.
-    pub final_lsn: i64,
+    pub final_lsn: Lsn,
     /// Commit timestamp of the transaction.
     pub timestamp: DateTime<Utc>,
     /// Xid of the transaction.
-    pub transaction_id: i32,
+    pub transaction_id: Xid,
 }
 
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct GenericMessage<'a> {
-    /// Xid of the transaction (only present for streamed transactions).
+    /// Xid of the transaction (only present for transactional messages of streamed transactions).
     ///
-    /// NOTE: This field is available since protocol version 2.
-    pub transaction_id: Option<i32>,
+    /// NOTE: This field is available since protocol version 2. Non-transactional messages are
+    /// sent as soon as they are decoded, outside of any `Begin`/`Commit` or stream block, so
+    /// they never carry an xid.
+    pub transaction_id: Option<Xid>,
     /// If the logical decoding message is transactional?
     pub is_transactional: bool,
     /// The LSN of the logical decoding message.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// The prefix of the logical decoding message.
     pub prefix: String,
     /// Length of the content.
     pub length: i32,
     /// The content of the logical decoding message.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_base64"))]
     pub content: &'a [u8],
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CommitMessage {
     /// The LSN of the commit.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// The final LSN of the transaction.
-    pub final_lsn: i64,
+    pub final_lsn: Lsn,
     /// Commit timestamp of the transaction.
     pub timestamp: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct OriginMessage {
     /// The LSN of the commit on the origin server.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// Name of the origin.
     ///
     /// NOTE: There can be multiple Origin messages inside a single transaction.
     pub name: String,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RelationMessage {
     /// Xid of the transaction (only present for streamed transactions).
     ///
     /// NOTE: This field is available since protocol version 2.
-    pub transaction_id: Option<i32>,
+    pub transaction_id: Option<Xid>,
     /// OID of the relation.
     pub oid: i32,
     /// Namespace (`None` for `pg_catalog`).
@@ -79,11 +318,108 @@ pub struct RelationMessage {
     /// Relation name.
     pub name: String,
     /// Replica identity setting for the relation (same as `relreplident` in `pg_class`).
-    pub replica_identity: i8,
+    pub replica_identity: ReplicaIdentity,
     /// Columns.
     pub columns: Vec<RelationMessageColumn>,
 }
 
+impl RelationMessage {
+    /// The identity of the relation, for use as a map key.
+    pub fn key(&self) -> RelationKey {
+        RelationKey {
+            oid: self.oid,
+            transaction_id: self.transaction_id,
+        }
+    }
+
+    /// Columns that are part of the key, in the order of the relation.
+    pub fn key_columns(&self) -> Vec<&RelationMessageColumn> {
+        self.columns
+            .iter()
+            .filter(|column| column.is_part_of_the_key)
+            .collect()
+    }
+
+    /// Returns the column with the given name.
+    pub fn column_by_name(&self, name: &str) -> Option<&RelationMessageColumn> {
+        self.columns.iter().find(|column| column.name == name)
+    }
+
+    /// Returns the position of the column with the given name, which is also its position in
+    /// the tuples of the relation.
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        self.columns.iter().position(|column| column.name == name)
+    }
+
+    /// Checks that the tuple has as many columns as the relation.
+    pub fn validate_tuple(&self, tuple: &TupleData) -> Result<(), MismatchError> {
+        if tuple.columns.len() != self.columns.len() {
+            return Err(MismatchError {
+                expected: self.columns.len(),
+                actual: tuple.columns.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+/// Identity of a [`RelationMessage`], usable as a map key.
+///
+/// Relations sent inside a streamed transaction are only valid within that transaction, so
+/// its xid is part of the key. For all other relations `transaction_id` is `None` and the
+/// key is just the OID.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelationKey {
+    /// OID of the relation.
+    pub oid: i32,
+    /// Xid of the streamed transaction the relation was sent in.
+    pub transaction_id: Option<Xid>,
+}
+
+/// Replica identity setting of a relation (`relreplident` in `pg_class`).
+///
+/// It determines which old tuple data is sent with [`UpdateMessage`] and [`DeleteMessage`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ReplicaIdentity {
+    /// Columns of the primary key, if any, are sent as `key` (`'d'`).
+    Default,
+    /// No old tuple data is sent (`'n'`).
+    Nothing,
+    /// All columns of the old row are sent as `old` (`'f'`).
+    Full,
+    /// Columns of the replica identity index are sent as `key` (`'i'`).
+    Index,
+}
+
+impl ReplicaIdentity {
+    /// Converts a `relreplident` byte, returning `None` for unknown values.
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'd' => Some(Self::Default),
+            b'n' => Some(Self::Nothing),
+            b'f' => Some(Self::Full),
+            b'i' => Some(Self::Index),
+            _ => None,
+        }
+    }
+
+    /// Converts to a `relreplident` byte.
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Self::Default => b'd',
+            Self::Nothing => b'n',
+            Self::Full => b'f',
+            Self::Index => b'i',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RelationMessageColumn {
     /// Is part of the key?
     pub is_part_of_the_key: bool,
@@ -95,11 +431,13 @@ pub struct RelationMessageColumn {
     pub type_modifier: i32,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TypeMessage {
     /// Xid of the transaction (only present for streamed transactions).
     ///
     /// NOTE: This field is available since protocol version 2.
-    pub transaction_id: Option<i32>,
+    pub transaction_id: Option<Xid>,
     /// OID of the relation.
     pub oid: i32,
     /// Namespace (`None` for `pg_catalog`).
@@ -108,37 +446,119 @@ pub struct TypeMessage {
     pub name: String,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct InsertMessage<'a> {
     /// Xid of the transaction (only present for streamed transactions).
     ///
     /// NOTE: This field is available since protocol version 2.
-    pub transaction_id: Option<i32>,
+    pub transaction_id: Option<Xid>,
     /// OID of the relation.
     pub oid: i32,
     /// [`TupleData`] message part representing the contents of new tuple.
     pub data: TupleData<'a>,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UpdateMessage<'a> {
     /// Xid of the transaction (only present for streamed transactions).
     ///
     /// NOTE: This field is available since protocol version 2.
-    pub transaction_id: Option<i32>,
+    pub transaction_id: Option<Xid>,
     /// OID of the relation corresponding to the ID in the relation message.
     pub oid: i32,
     /// This field is optional and is only present if the update changed data in any of the column(s) that are part of the REPLICA IDENTITY index.
     pub key: Option<TupleData<'a>>,
     /// This field is optional and is only present if table in which the update happened has REPLICA IDENTITY set to FULL.
+    ///
+    /// NOTE: Under any other replica identity the old values of non-key columns are never sent,
+    /// see [`UpdateMessage::before_image`].
     pub old: Option<TupleData<'a>>,
     /// TupleData message part representing the contents of a new tuple.
     pub new: TupleData<'a>,
 }
 
+impl<'a> UpdateMessage<'a> {
+    /// Pairs the values of the `key` tuple with the key columns of the relation.
+    ///
+    /// Returns `None` if the key tuple is absent, i.e. the update didn't change the replica
+    /// identity (or the relation has REPLICA IDENTITY FULL and sent `old` instead).
+    pub fn key_columns<'r>(
+        &self,
+        relation: &'r RelationMessage,
+    ) -> Option<Vec<(&'r RelationMessageColumn, &TupleDataColumn<'a>)>> {
+        self.key.as_ref().map(|key| key.key_columns(relation))
+    }
+
+    /// The old row as far as it's known, pairing every column of the relation with its old
+    /// value, or `None` if that wasn't sent.
+    ///
+    /// With REPLICA IDENTITY FULL the whole old row is sent in `old`. Otherwise only the old
+    /// values of the replica identity columns are known: from `key` if the update changed them,
+    /// or else from `new`, as they're unchanged. Under REPLICA IDENTITY NOTHING no old value is
+    /// known.
+    pub fn before_image<'r>(
+        &self,
+        relation: &'r RelationMessage,
+    ) -> Vec<(&'r RelationMessageColumn, Option<&TupleDataColumn<'a>>)> {
+        if let Some(old) = &self.old {
+            return relation
+                .columns
+                .iter()
+                .zip(old.columns.iter().map(Some).chain(core::iter::repeat(None)))
+                .collect();
+        }
+
+        let key = self.key_columns(relation);
+        relation
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(i, column)| {
+                let value = match &key {
+                    _ if !column.is_part_of_the_key => None,
+                    Some(key) => key
+                        .iter()
+                        .find(|(key_column, _)| ptr::eq(*key_column, column))
+                        .map(|(_, value)| *value),
+                    None => self.new.columns.get(i),
+                };
+
+                (column, value)
+            })
+            .collect()
+    }
+
+    /// Positions of the columns whose values differ between the `old` and the `new` tuple.
+    ///
+    /// Returns `None` if the old tuple is absent, i.e. the relation doesn't have
+    /// REPLICA IDENTITY FULL. Unchanged TOASTed values in the new tuple are not reported.
+    pub fn changed_columns(&self) -> Option<Vec<usize>> {
+        let old = self.old.as_ref()?;
+
+        let changed = self
+            .new
+            .columns
+            .iter()
+            .enumerate()
+            .filter(|(i, column)| {
+                !column.is_unchanged_toast() && old.columns.get(*i) != Some(column)
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        Some(changed)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DeleteMessage<'a> {
     /// Xid of the transaction (only present for streamed transactions).
     ///
     /// NOTE: This field is available since protocol version 2.
-    pub transaction_id: Option<i32>,
+    pub transaction_id: Option<Xid>,
     /// OID of the relation corresponding to the ID in the relation message.
     pub oid: i32,
     /// This field is optional and is only present if the update changed data in any of the column(s) that are part of the REPLICA IDENTITY index.
@@ -147,134 +567,349 @@ pub struct DeleteMessage<'a> {
     pub old: Option<TupleData<'a>>,
 }
 
+impl<'a> DeleteMessage<'a> {
+    /// Pairs the values of the `key` tuple with the key columns of the relation.
+    ///
+    /// Returns `None` if the key tuple is absent, i.e. the relation has REPLICA IDENTITY FULL
+    /// and sent `old` instead.
+    pub fn key_columns<'r>(
+        &self,
+        relation: &'r RelationMessage,
+    ) -> Option<Vec<(&'r RelationMessageColumn, &TupleDataColumn<'a>)>> {
+        self.key.as_ref().map(|key| key.key_columns(relation))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct TruncateMessage {
     /// Xid of the transaction (only present for streamed transactions).
     ///
     /// NOTE: This field is available since protocol version 2.
-    pub transaction_id: Option<i32>,
-    /// Number of relations
-    pub relations_count: i32,
+    pub transaction_id: Option<Xid>,
     /// Is `CASCADE`?
     pub is_cascade: bool,
     /// Is `RESTART IDENTITY`?
     pub is_restart_identity: bool,
-    /// OID of the relation corresponding to the ID in the relation message.
-    pub oid: i32,
+    /// OIDs of the relations corresponding to the ID in the relation message.
+    pub oids: Vec<i32>,
+}
+
+impl TruncateMessage {
+    /// The options of the `TRUNCATE` command.
+    pub fn options(&self) -> TruncateOptions {
+        TruncateOptions {
+            cascade: self.is_cascade,
+            restart_identity: self.is_restart_identity,
+        }
+    }
+
+    /// Number of relations.
+    pub fn relations_count(&self) -> usize {
+        self.oids.len()
+    }
 }
 
+/// Options of a `TRUNCATE` command, sent as a bitmask byte in [`TruncateMessage`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TruncateOptions {
+    /// Is `CASCADE`?
+    pub cascade: bool,
+    /// Is `RESTART IDENTITY`?
+    pub restart_identity: bool,
+}
+
+impl TruncateOptions {
+    /// Bit of the `CASCADE` option.
+    pub const CASCADE: u8 = 1 << 0;
+    /// Bit of the `RESTART IDENTITY` option.
+    pub const RESTART_IDENTITY: u8 = 1 << 1;
+
+    /// Converts the bitmask byte, ignoring unknown bits.
+    pub fn from_bits(bits: u8) -> Self {
+        Self {
+            cascade: bits & Self::CASCADE != 0,
+            restart_identity: bits & Self::RESTART_IDENTITY != 0,
+        }
+    }
+
+    /// Converts the options to the bitmask byte.
+    pub fn bits(self) -> u8 {
+        let mut bits = 0;
+        if self.cascade {
+            bits |= Self::CASCADE;
+        }
+        if self.restart_identity {
+            bits |= Self::RESTART_IDENTITY;
+        }
+        bits
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StreamStartMessage {
     /// Xid of the transaction (only present for streamed transactions).
     ///
     /// NOTE: This field is available since protocol version 2.
-    pub transaction_id: Option<i32>,
+    pub transaction_id: Option<Xid>,
     /// Is it a first stream segment?
     pub is_first_segment: bool,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StreamStopMessage {}
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StreamCommitMessage {
     /// Xid of the transaction.
-    pub transaction_id: i32,
+    pub transaction_id: Xid,
     /// The LSN of the commit.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// The end LSN of the transaction.
-    pub final_lsn: i64,
+    pub final_lsn: Lsn,
     /// Commit timestamp of the transaction.
     pub timestamp: DateTime<Utc>,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StreamAbortMessage {
     /// Xid of the transaction.
-    pub transaction_id: i32,
+    pub transaction_id: Xid,
     /// Xid of the subtransaction (will be same as xid of the transaction for top-level transactions).
-    pub subtransaction_id: i32,
+    pub subtransaction_id: Xid,
+    /// The LSN of the abort operation.
+    ///
+    /// NOTE: This field is available since protocol version 4, and only present when `streaming`
+    /// is set to `parallel` (see [`ParseOptions::parallel_streaming`]).
+    pub abort_lsn: Option<Lsn>,
+    /// Abort timestamp of the transaction.
+    ///
+    /// NOTE: This field is available since protocol version 4, and only present when `streaming`
+    /// is set to `parallel`.
+    pub abort_timestamp: Option<DateTime<Utc>>,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct BeginPrepareMessage {
     /// The LSN of the prepare.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// The end LSN of the prepared transaction.
-    pub final_lsn: i64,
+    pub final_lsn: Lsn,
     /// Prepare timestamp of the transaction.
     pub timestamp: DateTime<Utc>,
     /// Xid of the transaction.
-    pub transaction_id: i32,
+    pub transaction_id: Xid,
     /// The user defined GID of the prepared transaction.
     pub gid: String,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct PrepareMessage {
     /// The LSN of the prepare.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// The end LSN of the prepared transaction.
-    pub final_lsn: i64,
+    pub final_lsn: Lsn,
     /// Prepare timestamp of the transaction.
     pub timestamp: DateTime<Utc>,
     /// Xid of the transaction.
-    pub transaction_id: i32,
+    pub transaction_id: Xid,
     /// The user defined GID of the prepared transaction.
     pub gid: String,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct CommitPreparedMessage {
     /// The LSN of the commit.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// The end LSN of the prepared transaction.
-    pub final_lsn: i64,
+    pub final_lsn: Lsn,
     /// Commit timestamp of the transaction.
     pub timestamp: DateTime<Utc>,
     /// Xid of the transaction.
-    pub transaction_id: i32,
+    pub transaction_id: Xid,
     /// The user defined GID of the prepared transaction.
     pub gid: String,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RollbackPreparedMessage {
     /// The LSN of the rollback.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// The end LSN of the rollback or the prepared transaction.
-    pub final_lsn: i64,
+    pub final_lsn: Lsn,
     /// Prepare timestamp of the transaction.
     pub prepare_timestamp: DateTime<Utc>,
     /// Rollback timestamp of the transaction.
     pub timestamp: DateTime<Utc>,
     /// Xid of the transaction.
-    pub transaction_id: i32,
+    pub transaction_id: Xid,
     /// The user defined GID of the prepared transaction.
     pub gid: String,
 }
 
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct StreamPrepareMessage {
     /// The LSN of the prepare.
-    pub lsn: i64,
+    pub lsn: Lsn,
     /// The end LSN of the prepared transaction.
-    pub final_lsn: i64,
+    pub final_lsn: Lsn,
     /// Prepare timestamp of the transaction.
     pub timestamp: DateTime<Utc>,
     /// Xid of the transaction.
-    pub transaction_id: i32,
+    pub transaction_id: Xid,
     /// The user defined GID of the prepared transaction.
     pub gid: String,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct TupleData<'a> {
     /// Columns.
     pub columns: Vec<TupleDataColumn<'a>>,
 }
 
+impl<'a> TupleData<'a> {
+    /// Iterates over the columns paired with the column names of their relation.
+    ///
+    /// The tuple is expected to have as many columns as the relation (see
+    /// [`RelationMessage::validate_tuple`]); extra columns on either side are skipped.
+    pub fn iter_named<'r>(
+        &self,
+        relation: &'r RelationMessage,
+    ) -> impl Iterator<Item = (&'r str, &TupleDataColumn<'a>)> {
+        debug_assert_eq!(
+            self.columns.len(),
+            relation.columns.len(),
+            "tuple and relation column counts differ"
+        );
+
+        relation
+            .columns
+            .iter()
+            .map(|column| column.name.as_str())
+            .zip(&self.columns)
+    }
+
+    /// Maps the column names of the relation to the columns, for random access by name like
+    /// `row["email"]`.
+    ///
+    /// Pairs columns like [`TupleData::iter_named`]. If the relation has duplicate column names
+    /// (which Postgres doesn't allow), the first column with the name is kept.
+    ///
+    /// NOTE: This is a [`BTreeMap`] so that it's available without `std`, which also keeps
+    /// the names sorted. See [`TupleData::as_hash_map`] for a `HashMap`.
+    pub fn as_map<'r>(
+        &self,
+        relation: &'r RelationMessage,
+    ) -> BTreeMap<&'r str, &TupleDataColumn<'a>> {
+        let mut map = BTreeMap::new();
+        for (name, column) in self.iter_named(relation) {
+            map.entry(name).or_insert(column);
+        }
+
+        map
+    }
+
+    /// Maps the column names of the relation to the columns like [`TupleData::as_map`], into a
+    /// [`HashMap`](std::collections::HashMap).
+    #[cfg(feature = "std")]
+    pub fn as_hash_map<'r>(
+        &self,
+        relation: &'r RelationMessage,
+    ) -> std::collections::HashMap<&'r str, &TupleDataColumn<'a>> {
+        let mut map = std::collections::HashMap::with_capacity(self.columns.len());
+        for (name, column) in self.iter_named(relation) {
+            map.entry(name).or_insert(column);
+        }
+
+        map
+    }
+
+    /// Pairs the values of a key tuple with the columns of the relation flagged as part of the
+    /// key.
+    ///
+    /// Postgres sends key tuples with a value (NULL for non-key columns) for every relation
+    /// column, in which case the non-key columns are skipped. A tuple holding only the key
+    /// values, e.g. for REPLICA IDENTITY USING INDEX, is paired with the key columns in order.
+    fn key_columns<'r>(
+        &self,
+        relation: &'r RelationMessage,
+    ) -> Vec<(&'r RelationMessageColumn, &TupleDataColumn<'a>)> {
+        if self.columns.len() == relation.columns.len() {
+            relation
+                .columns
+                .iter()
+                .zip(&self.columns)
+                .filter(|(column, _)| column.is_part_of_the_key)
+                .collect()
+        } else {
+            relation
+                .columns
+                .iter()
+                .filter(|column| column.is_part_of_the_key)
+                .zip(&self.columns)
+                .collect()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
 pub struct TupleDataColumn<'a> {
+    /// Kind of the column data, holding the value if one was sent.
+    pub kind: TupleDataKind<'a>,
+}
+
+impl<'a> TupleDataColumn<'a> {
+    /// Is the data a NULL value?
+    pub fn is_null(&self) -> bool {
+        matches!(self.kind, TupleDataKind::Null)
+    }
+
+    /// Is the data an unchanged TOASTed value?
+    pub fn is_unchanged_toast(&self) -> bool {
+        matches!(self.kind, TupleDataKind::UnchangedToast)
+    }
+
+    /// The value of the column as text. Only present for text formatted values.
+    pub fn as_text(&self) -> Option<&str> {
+        match &self.kind {
+            TupleDataKind::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of the column in bytes. Only present for binary formatted values.
+    pub fn as_binary(&self) -> Option<&'a [u8]> {
+        match self.kind {
+            TupleDataKind::Binary(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of a [`TupleDataColumn`] (`'n'`, `'u'`, `'t'` or `'b'` on the wire).
+#[derive(Clone, PartialEq)]
+pub enum TupleDataKind<'a> {
     /// Identifies the data as NULL value.
-    pub is_null: bool,
+    Null,
     /// Identifies unchanged TOASTed value (the actual value is not sent).
-    /// TODO: decide correct naming here after research
-    pub is_unchanged: bool,
+    UnchangedToast,
     /// Identifies the data as text formatted value.
-    pub is_text: bool,
+    ///
+    /// Parsed values are borrowed from the input buffer, only checked to be valid UTF-8.
+    Text(Cow<'a, str>),
     /// Identifies the data as binary formatted value.
-    pub is_binary: bool,
-    /// The value of the column in bytes. Only present if `is_binary` is `true`.
-    pub binary_value: Option<&'a [u8]>,
-    /// The value of the column as [`String`]. Only present if `is_text` is `true`,
-    pub text_value: Option<String>,
+    Binary(&'a [u8]),
 }
@@ -0,0 +1,99 @@
+use core::fmt;
+use core::str::FromStr;
+
+use crate::ParseLsnError;
+
+/// A WAL location (log sequence number).
+///
+/// Displayed and parsed in the Postgres `XXXXXXXX/XXXXXXXX` notation, e.g. `16/B374D848`.
+/// LSNs are byte positions in the WAL, so they're ordered and their difference is a number of
+/// bytes, e.g. how far a consumer lags behind the server.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Lsn(pub u64);
+
+impl Lsn {
+    /// The invalid LSN `0/0`, which Postgres uses for "no location".
+    pub const INVALID: Self = Self(0);
+
+    /// Is this a valid location, i.e. not [`Lsn::INVALID`]?
+    pub fn is_valid(self) -> bool {
+        self != Self::INVALID
+    }
+
+    /// Number of bytes from `other` to `self`, or 0 if `other` is ahead of `self`.
+    pub fn saturating_sub(self, other: Self) -> u64 {
+        self.0.saturating_sub(other.0)
+    }
+
+    /// Number of bytes from `other` to `self`, or `None` if `other` is ahead of `self`.
+    pub fn checked_sub(self, other: Self) -> Option<u64> {
+        self.0.checked_sub(other.0)
+    }
+
+    /// Advances the location by `bytes`, or returns `None` if that overflows.
+    pub fn checked_add(self, bytes: u64) -> Option<Self> {
+        self.0.checked_add(bytes).map(Self)
+    }
+}
+
+impl fmt::Display for Lsn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:X}/{:X}", self.0 >> 32, self.0 as u32)
+    }
+}
+
+impl FromStr for Lsn {
+    type Err = ParseLsnError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (high, low) = s.split_once('/').ok_or(ParseLsnError)?;
+        let parse = |part: &str| {
+            if part.is_empty() || part.len() > 8 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(ParseLsnError);
+            }
+
+            u32::from_str_radix(part, 16).map_err(|_| ParseLsnError)
+        };
+
+        Ok(Self(
+            (u64::from(parse(high)?) << 32) | u64::from(parse(low)?),
+        ))
+    }
+}
+
+impl From<u64> for Lsn {
+    fn from(value: u64) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Lsn> for u64 {
+    fn from(lsn: Lsn) -> Self {
+        lsn.0
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Lsn {
+    /// Serializes the LSN in the `XXX/XXX` text form, or as a `u64` for binary formats.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            serializer.serialize_u64(self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Lsn {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if !deserializer.is_human_readable() {
+            return <u64 as serde::Deserialize>::deserialize(deserializer).map(Self);
+        }
+
+        let lsn = <alloc::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+
+        lsn.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -0,0 +1,51 @@
+use core::fmt;
+
+use crate::value::{fixed, FromValue};
+use crate::{TupleDataColumn, ValueDecodeError};
+
+/// A Postgres `macaddr` value, a 6-byte MAC address.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MacAddr(pub [u8; 6]);
+
+impl fmt::Display for MacAddr {
+    /// Formats the address like Postgres does, e.g. `08:00:2b:01:02:03`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let [a, b, c, d, e, g] = self.0;
+        write!(f, "{a:02x}:{b:02x}:{c:02x}:{d:02x}:{e:02x}:{g:02x}")
+    }
+}
+
+impl TupleDataColumn<'_> {
+    /// Decodes a `macaddr` value (e.g. `08:00:2b:01:02:03` in text format).
+    pub fn as_macaddr(&self) -> Result<MacAddr, ValueDecodeError> {
+        self.value()
+    }
+}
+
+impl FromValue for MacAddr {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        const INVALID: ValueDecodeError = ValueDecodeError::InvalidValue {
+            type_name: "macaddr",
+        };
+
+        let mut address = [0; 6];
+        let mut parts = text.split(':');
+        for byte in &mut address {
+            let part = parts.next().ok_or(INVALID)?;
+            if part.len() != 2 || !part.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(INVALID);
+            }
+            *byte = u8::from_str_radix(part, 16).map_err(|_| INVALID)?;
+        }
+        if parts.next().is_some() {
+            return Err(INVALID);
+        }
+
+        Ok(Self(address))
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        fixed(bytes).map(Self)
+    }
+}
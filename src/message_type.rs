@@ -0,0 +1,110 @@
+use crate::ReplicationMessage;
+
+/// Type of a [`ReplicationMessage`], without its contents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum MessageType {
+    Begin,
+    Generic,
+    Commit,
+    Origin,
+    Relation,
+    Type,
+    Insert,
+    Update,
+    Delete,
+    Truncate,
+    StreamStart,
+    StreamStop,
+    StreamCommit,
+    StreamAbort,
+    BeginPrepare,
+    Prepare,
+    CommitPrepared,
+    RollbackPrepared,
+    StreamPrepare,
+    /// See [`ReplicationMessage::Unknown`].
+    Unknown,
+}
+
+impl ReplicationMessage<'_> {
+    /// Returns the type of the message.
+    pub fn message_type(&self) -> MessageType {
+        match self {
+            Self::Begin(_) => MessageType::Begin,
+            Self::Generic(_) => MessageType::Generic,
+            Self::Commit(_) => MessageType::Commit,
+            Self::Origin(_) => MessageType::Origin,
+            Self::Relation(_) => MessageType::Relation,
+            Self::Type(_) => MessageType::Type,
+            Self::Insert(_) => MessageType::Insert,
+            Self::Update(_) => MessageType::Update,
+            Self::Delete(_) => MessageType::Delete,
+            Self::Truncate(_) => MessageType::Truncate,
+            Self::StreamStart(_) => MessageType::StreamStart,
+            Self::StreamStop(_) => MessageType::StreamStop,
+            Self::StreamCommit(_) => MessageType::StreamCommit,
+            Self::StreamAbort(_) => MessageType::StreamAbort,
+            Self::BeginPrepare(_) => MessageType::BeginPrepare,
+            Self::Prepare(_) => MessageType::Prepare,
+            Self::CommitPrepared(_) => MessageType::CommitPrepared,
+            Self::RollbackPrepared(_) => MessageType::RollbackPrepared,
+            Self::StreamPrepare(_) => MessageType::StreamPrepare,
+            Self::Unknown { .. } => MessageType::Unknown,
+        }
+    }
+
+    /// Returns the message-type byte the message starts with on the wire.
+    pub fn message_type_byte(&self) -> u8 {
+        match self {
+            Self::Begin(_) => b'B',
+            Self::Generic(_) => b'M',
+            Self::Commit(_) => b'C',
+            Self::Origin(_) => b'O',
+            Self::Relation(_) => b'R',
+            Self::Type(_) => b'Y',
+            Self::Insert(_) => b'I',
+            Self::Update(_) => b'U',
+            Self::Delete(_) => b'D',
+            Self::Truncate(_) => b'T',
+            Self::StreamStart(_) => b'S',
+            Self::StreamStop(_) => b'E',
+            Self::StreamCommit(_) => b'c',
+            Self::StreamAbort(_) => b'A',
+            Self::BeginPrepare(_) => b'b',
+            Self::Prepare(_) => b'P',
+            Self::CommitPrepared(_) => b'K',
+            Self::RollbackPrepared(_) => b'r',
+            Self::StreamPrepare(_) => b'p',
+            Self::Unknown { tag, .. } => *tag,
+        }
+    }
+
+    /// Whether the message starts or ends a transaction: `Begin`, `Commit`, the messages of
+    /// two-phase commit, and `StreamCommit`, `StreamPrepare` or the `StreamAbort` of a whole
+    /// streamed transaction.
+    ///
+    /// `StreamStart` and `StreamStop` only delimit chunks of a streamed transaction, and the
+    /// `StreamAbort` of a subtransaction doesn't end its transaction, so they're not boundaries.
+    pub fn is_transaction_boundary(&self) -> bool {
+        match self {
+            Self::Begin(_)
+            | Self::Commit(_)
+            | Self::BeginPrepare(_)
+            | Self::Prepare(_)
+            | Self::CommitPrepared(_)
+            | Self::RollbackPrepared(_)
+            | Self::StreamCommit(_)
+            | Self::StreamPrepare(_) => true,
+            Self::StreamAbort(message) => message.transaction_id == message.subtransaction_id,
+            _ => false,
+        }
+    }
+
+    /// Whether the message changes rows: an insert, update, delete or truncate.
+    pub fn is_data_change(&self) -> bool {
+        self.op().is_some()
+    }
+}
@@ -0,0 +1,73 @@
+use crate::ProtocolVersion;
+
+/// Options of [`ReplicationMessage::parse_with_options`](crate::ReplicationMessage::parse_with_options).
+///
+/// Start from [`ParseOptions::default`] and change it with the setters, e.g.
+/// `ParseOptions::default().with_version(ProtocolVersion::V2).lossy_utf8(true)`.
+///
+/// NOTE: More options may be added, so the struct can't be built with a literal outside of this
+/// crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct ParseOptions {
+    /// Fail on anything unexpected (the default).
+    ///
+    /// When unset, unknown message types are returned as
+    /// [`ReplicationMessage::Unknown`](crate::ReplicationMessage::Unknown), and optional trailing
+    /// fields that are cut off are ignored instead of failing with
+    /// [`ParseError::UnexpectedEof`](crate::ParseError::UnexpectedEof).
+    pub strict: bool,
+    /// Protocol version the messages were sent with.
+    pub version: ProtocolVersion,
+    /// Decode text values that aren't valid UTF-8 lossily, replacing invalid sequences with
+    /// U+FFFD like [`String::from_utf8_lossy`](alloc::string::String::from_utf8_lossy), instead
+    /// of failing with [`ParseError::InvalidUtf8`](crate::ParseError::InvalidUtf8) (unset by
+    /// default).
+    ///
+    /// NOTE: Only applies to the values of tuple columns. Names and other strings of the
+    /// messages are always validated.
+    pub lossy_utf8: bool,
+    /// The subscription was started with `streaming = parallel` (unset by default), so
+    /// [`StreamAbortMessage`](crate::StreamAbortMessage)s carry the abort LSN and timestamp.
+    ///
+    /// NOTE: Only applies since protocol version 4. It can't be told from the messages
+    /// themselves, since they aren't framed with their length.
+    pub parallel_streaming: bool,
+}
+
+impl Default for ParseOptions {
+    fn default() -> Self {
+        Self {
+            strict: true,
+            version: ProtocolVersion::default(),
+            lossy_utf8: false,
+            parallel_streaming: false,
+        }
+    }
+}
+
+impl ParseOptions {
+    /// Sets [`ParseOptions::strict`].
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets [`ParseOptions::version`].
+    pub fn with_version(mut self, version: ProtocolVersion) -> Self {
+        self.version = version;
+        self
+    }
+
+    /// Sets [`ParseOptions::lossy_utf8`].
+    pub fn lossy_utf8(mut self, lossy_utf8: bool) -> Self {
+        self.lossy_utf8 = lossy_utf8;
+        self
+    }
+
+    /// Sets [`ParseOptions::parallel_streaming`].
+    pub fn parallel_streaming(mut self, parallel_streaming: bool) -> Self {
+        self.parallel_streaming = parallel_streaming;
+        self
+    }
+}
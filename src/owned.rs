@@ -0,0 +1,385 @@
+use crate::*;
+
+/// An owned [`ReplicationMessage`], not borrowing from the input buffer.
+///
+/// Like all messages and decoders of this crate, it's `Send + Sync`, so it can be handed over to
+/// other tasks or kept in shared state.
+#[derive(Debug, Clone, PartialEq)]
+#[non_exhaustive]
+pub enum OwnedReplicationMessage {
+    Begin(BeginMessage),
+    Generic(OwnedGenericMessage),
+    Commit(CommitMessage),
+    Origin(OriginMessage),
+    Relation(RelationMessage),
+    Type(TypeMessage),
+    Insert(OwnedInsertMessage),
+    Update(OwnedUpdateMessage),
+    Delete(OwnedDeleteMessage),
+    Truncate(TruncateMessage),
+    StreamStart(StreamStartMessage),
+    StreamStop(StreamStopMessage),
+    StreamCommit(StreamCommitMessage),
+    StreamAbort(StreamAbortMessage),
+    BeginPrepare(BeginPrepareMessage),
+    Prepare(PrepareMessage),
+    CommitPrepared(CommitPreparedMessage),
+    RollbackPrepared(RollbackPreparedMessage),
+    StreamPrepare(StreamPrepareMessage),
+    /// An owned [`ReplicationMessage::Unknown`].
+    Unknown {
+        /// The message-type byte.
+        tag: u8,
+        /// The rest of the buffer after the tag.
+        payload: Vec<u8>,
+    },
+}
+
+/// An owned [`GenericMessage`].
+#[derive(Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedGenericMessage {
+    /// Xid of the transaction (only present for transactional messages of streamed transactions).
+    ///
+    /// NOTE: This field is available since protocol version 2. Non-transactional messages are
+    /// sent as soon as they are decoded, outside of any `Begin`/`Commit` or stream block, so
+    /// they never carry an xid.
+    pub transaction_id: Option<Xid>,
+    /// If the logical decoding message is transactional?
+    pub is_transactional: bool,
+    /// The LSN of the logical decoding message.
+    pub lsn: Lsn,
+    /// The prefix of the logical decoding message.
+    pub prefix: String,
+    /// Length of the content.
+    pub length: i32,
+    /// The content of the logical decoding message.
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_base64"))]
+    pub content: Vec<u8>,
+}
+
+/// An owned [`InsertMessage`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedInsertMessage {
+    /// Xid of the transaction (only present for streamed transactions).
+    ///
+    /// NOTE: This field is available since protocol version 2.
+    pub transaction_id: Option<Xid>,
+    /// OID of the relation.
+    pub oid: i32,
+    /// [`OwnedTupleData`] message part representing the contents of new tuple.
+    pub data: OwnedTupleData,
+}
+
+/// An owned [`UpdateMessage`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedUpdateMessage {
+    /// Xid of the transaction (only present for streamed transactions).
+    ///
+    /// NOTE: This field is available since protocol version 2.
+    pub transaction_id: Option<Xid>,
+    /// OID of the relation corresponding to the ID in the relation message.
+    pub oid: i32,
+    /// This field is optional and is only present if the update changed data in any of the column(s) that are part of the REPLICA IDENTITY index.
+    pub key: Option<OwnedTupleData>,
+    /// This field is optional and is only present if table in which the update happened has REPLICA IDENTITY set to FULL.
+    pub old: Option<OwnedTupleData>,
+    /// TupleData message part representing the contents of a new tuple.
+    pub new: OwnedTupleData,
+}
+
+/// An owned [`DeleteMessage`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedDeleteMessage {
+    /// Xid of the transaction (only present for streamed transactions).
+    ///
+    /// NOTE: This field is available since protocol version 2.
+    pub transaction_id: Option<Xid>,
+    /// OID of the relation corresponding to the ID in the relation message.
+    pub oid: i32,
+    /// This field is optional and is only present if the update changed data in any of the column(s) that are part of the REPLICA IDENTITY index.
+    pub key: Option<OwnedTupleData>,
+    /// This field is optional and is only present if table in which the update happened has REPLICA IDENTITY set to FULL.
+    pub old: Option<OwnedTupleData>,
+}
+
+/// An owned [`TupleData`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedTupleData {
+    /// Columns.
+    pub columns: Vec<OwnedTupleDataColumn>,
+}
+
+/// An owned [`TupleDataColumn`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct OwnedTupleDataColumn {
+    /// Kind of the column data, holding the value if one was sent.
+    pub kind: OwnedTupleDataKind,
+}
+
+impl OwnedTupleDataColumn {
+    /// Is the data a NULL value?
+    pub fn is_null(&self) -> bool {
+        matches!(self.kind, OwnedTupleDataKind::Null)
+    }
+
+    /// Is the data an unchanged TOASTed value?
+    pub fn is_unchanged_toast(&self) -> bool {
+        matches!(self.kind, OwnedTupleDataKind::UnchangedToast)
+    }
+
+    /// The value of the column as text. Only present for text formatted values.
+    pub fn as_text(&self) -> Option<&str> {
+        match &self.kind {
+            OwnedTupleDataKind::Text(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The value of the column in bytes. Only present for binary formatted values.
+    pub fn as_binary(&self) -> Option<&[u8]> {
+        match &self.kind {
+            OwnedTupleDataKind::Binary(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// An owned [`TupleDataKind`].
+#[derive(Clone, PartialEq)]
+pub enum OwnedTupleDataKind {
+    /// Identifies the data as NULL value.
+    Null,
+    /// Identifies unchanged TOASTed value (the actual value is not sent).
+    UnchangedToast,
+    /// Identifies the data as text formatted value.
+    Text(String),
+    /// Identifies the data as binary formatted value.
+    Binary(Vec<u8>),
+}
+
+impl ReplicationMessage<'_> {
+    /// Copies all borrowed data, producing a message that doesn't borrow from the input buffer.
+    pub fn to_owned(&self) -> OwnedReplicationMessage {
+        match self {
+            Self::Begin(message) => OwnedReplicationMessage::Begin(message.clone()),
+            Self::Generic(message) => OwnedReplicationMessage::Generic(message.to_owned()),
+            Self::Commit(message) => OwnedReplicationMessage::Commit(message.clone()),
+            Self::Origin(message) => OwnedReplicationMessage::Origin(message.clone()),
+            Self::Relation(message) => OwnedReplicationMessage::Relation(message.clone()),
+            Self::Type(message) => OwnedReplicationMessage::Type(message.clone()),
+            Self::Insert(message) => OwnedReplicationMessage::Insert(message.to_owned()),
+            Self::Update(message) => OwnedReplicationMessage::Update(message.to_owned()),
+            Self::Delete(message) => OwnedReplicationMessage::Delete(message.to_owned()),
+            Self::Truncate(message) => OwnedReplicationMessage::Truncate(message.clone()),
+            Self::StreamStart(message) => OwnedReplicationMessage::StreamStart(message.clone()),
+            Self::StreamStop(message) => OwnedReplicationMessage::StreamStop(message.clone()),
+            Self::StreamCommit(message) => OwnedReplicationMessage::StreamCommit(message.clone()),
+            Self::StreamAbort(message) => OwnedReplicationMessage::StreamAbort(message.clone()),
+            Self::BeginPrepare(message) => OwnedReplicationMessage::BeginPrepare(message.clone()),
+            Self::Prepare(message) => OwnedReplicationMessage::Prepare(message.clone()),
+            Self::CommitPrepared(message) => {
+                OwnedReplicationMessage::CommitPrepared(message.clone())
+            }
+            Self::RollbackPrepared(message) => {
+                OwnedReplicationMessage::RollbackPrepared(message.clone())
+            }
+            Self::StreamPrepare(message) => OwnedReplicationMessage::StreamPrepare(message.clone()),
+            Self::Unknown { tag, payload } => OwnedReplicationMessage::Unknown {
+                tag: *tag,
+                payload: payload.to_vec(),
+            },
+        }
+    }
+}
+
+impl GenericMessage<'_> {
+    /// Copies the content, producing an [`OwnedGenericMessage`].
+    pub fn to_owned(&self) -> OwnedGenericMessage {
+        OwnedGenericMessage {
+            transaction_id: self.transaction_id,
+            is_transactional: self.is_transactional,
+            lsn: self.lsn,
+            prefix: self.prefix.clone(),
+            length: self.length,
+            content: self.content.to_vec(),
+        }
+    }
+}
+
+impl InsertMessage<'_> {
+    /// Copies the tuple data, producing an [`OwnedInsertMessage`].
+    pub fn to_owned(&self) -> OwnedInsertMessage {
+        OwnedInsertMessage {
+            transaction_id: self.transaction_id,
+            oid: self.oid,
+            data: self.data.to_owned(),
+        }
+    }
+}
+
+impl UpdateMessage<'_> {
+    /// Copies the tuple data, producing an [`OwnedUpdateMessage`].
+    pub fn to_owned(&self) -> OwnedUpdateMessage {
+        OwnedUpdateMessage {
+            transaction_id: self.transaction_id,
+            oid: self.oid,
+            key: self.key.as_ref().map(TupleData::to_owned),
+            old: self.old.as_ref().map(TupleData::to_owned),
+            new: self.new.to_owned(),
+        }
+    }
+}
+
+impl DeleteMessage<'_> {
+    /// Copies the tuple data, producing an [`OwnedDeleteMessage`].
+    pub fn to_owned(&self) -> OwnedDeleteMessage {
+        OwnedDeleteMessage {
+            transaction_id: self.transaction_id,
+            oid: self.oid,
+            key: self.key.as_ref().map(TupleData::to_owned),
+            old: self.old.as_ref().map(TupleData::to_owned),
+        }
+    }
+}
+
+impl TupleData<'_> {
+    /// Copies all column values, producing an [`OwnedTupleData`].
+    pub fn to_owned(&self) -> OwnedTupleData {
+        OwnedTupleData {
+            columns: self.columns.iter().map(TupleDataColumn::to_owned).collect(),
+        }
+    }
+}
+
+impl TupleDataColumn<'_> {
+    /// Copies the value, producing an [`OwnedTupleDataColumn`].
+    pub fn to_owned(&self) -> OwnedTupleDataColumn {
+        let kind = match &self.kind {
+            TupleDataKind::Null => OwnedTupleDataKind::Null,
+            TupleDataKind::UnchangedToast => OwnedTupleDataKind::UnchangedToast,
+            TupleDataKind::Text(value) => OwnedTupleDataKind::Text(String::from(&**value)),
+            TupleDataKind::Binary(value) => OwnedTupleDataKind::Binary(value.to_vec()),
+        };
+
+        OwnedTupleDataColumn { kind }
+    }
+}
+
+impl ReplicationMessage<'_> {
+    /// Converts the message to an [`OwnedReplicationMessage`], moving the data it already owns.
+    ///
+    /// Unlike [`ReplicationMessage::to_owned`], owned text values and strings are moved rather
+    /// than cloned, so only the data borrowed from the input buffer is copied.
+    pub fn into_owned(self) -> OwnedReplicationMessage {
+        match self {
+            Self::Begin(message) => OwnedReplicationMessage::Begin(message),
+            Self::Generic(message) => OwnedReplicationMessage::Generic(message.into_owned()),
+            Self::Commit(message) => OwnedReplicationMessage::Commit(message),
+            Self::Origin(message) => OwnedReplicationMessage::Origin(message),
+            Self::Relation(message) => OwnedReplicationMessage::Relation(message),
+            Self::Type(message) => OwnedReplicationMessage::Type(message),
+            Self::Insert(message) => OwnedReplicationMessage::Insert(message.into_owned()),
+            Self::Update(message) => OwnedReplicationMessage::Update(message.into_owned()),
+            Self::Delete(message) => OwnedReplicationMessage::Delete(message.into_owned()),
+            Self::Truncate(message) => OwnedReplicationMessage::Truncate(message),
+            Self::StreamStart(message) => OwnedReplicationMessage::StreamStart(message),
+            Self::StreamStop(message) => OwnedReplicationMessage::StreamStop(message),
+            Self::StreamCommit(message) => OwnedReplicationMessage::StreamCommit(message),
+            Self::StreamAbort(message) => OwnedReplicationMessage::StreamAbort(message),
+            Self::BeginPrepare(message) => OwnedReplicationMessage::BeginPrepare(message),
+            Self::Prepare(message) => OwnedReplicationMessage::Prepare(message),
+            Self::CommitPrepared(message) => OwnedReplicationMessage::CommitPrepared(message),
+            Self::RollbackPrepared(message) => OwnedReplicationMessage::RollbackPrepared(message),
+            Self::StreamPrepare(message) => OwnedReplicationMessage::StreamPrepare(message),
+            Self::Unknown { tag, payload } => OwnedReplicationMessage::Unknown {
+                tag,
+                payload: payload.to_vec(),
+            },
+        }
+    }
+}
+
+impl GenericMessage<'_> {
+    /// Converts the message to an [`OwnedGenericMessage`], moving the prefix.
+    pub fn into_owned(self) -> OwnedGenericMessage {
+        OwnedGenericMessage {
+            transaction_id: self.transaction_id,
+            is_transactional: self.is_transactional,
+            lsn: self.lsn,
+            prefix: self.prefix,
+            length: self.length,
+            content: self.content.to_vec(),
+        }
+    }
+}
+
+impl InsertMessage<'_> {
+    /// Converts the message to an [`OwnedInsertMessage`], moving owned text values.
+    pub fn into_owned(self) -> OwnedInsertMessage {
+        OwnedInsertMessage {
+            transaction_id: self.transaction_id,
+            oid: self.oid,
+            data: self.data.into_owned(),
+        }
+    }
+}
+
+impl UpdateMessage<'_> {
+    /// Converts the message to an [`OwnedUpdateMessage`], moving owned text values.
+    pub fn into_owned(self) -> OwnedUpdateMessage {
+        OwnedUpdateMessage {
+            transaction_id: self.transaction_id,
+            oid: self.oid,
+            key: self.key.map(TupleData::into_owned),
+            old: self.old.map(TupleData::into_owned),
+            new: self.new.into_owned(),
+        }
+    }
+}
+
+impl DeleteMessage<'_> {
+    /// Converts the message to an [`OwnedDeleteMessage`], moving owned text values.
+    pub fn into_owned(self) -> OwnedDeleteMessage {
+        OwnedDeleteMessage {
+            transaction_id: self.transaction_id,
+            oid: self.oid,
+            key: self.key.map(TupleData::into_owned),
+            old: self.old.map(TupleData::into_owned),
+        }
+    }
+}
+
+impl TupleData<'_> {
+    /// Converts the tuple to an [`OwnedTupleData`], moving owned text values.
+    pub fn into_owned(self) -> OwnedTupleData {
+        OwnedTupleData {
+            columns: self
+                .columns
+                .into_iter()
+                .map(TupleDataColumn::into_owned)
+                .collect(),
+        }
+    }
+}
+
+impl TupleDataColumn<'_> {
+    /// Converts the value to an [`OwnedTupleDataColumn`], moving an owned text value and copying
+    /// a borrowed one.
+    pub fn into_owned(self) -> OwnedTupleDataColumn {
+        let kind = match self.kind {
+            TupleDataKind::Null => OwnedTupleDataKind::Null,
+            TupleDataKind::UnchangedToast => OwnedTupleDataKind::UnchangedToast,
+            TupleDataKind::Text(value) => OwnedTupleDataKind::Text(value.into_owned()),
+            TupleDataKind::Binary(value) => OwnedTupleDataKind::Binary(value.to_vec()),
+        };
+
+        OwnedTupleDataColumn { kind }
+    }
+}
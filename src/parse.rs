@@ -0,0 +1,803 @@
+use alloc::borrow::{Cow, ToOwned};
+use core::ops::{Deref, DerefMut};
+
+use chrono::{DateTime, Utc};
+
+use crate::byte_reader::utf8;
+use crate::gid::validate_gid;
+use crate::*;
+
+impl<'a> ReplicationMessage<'a> {
+    /// Parses a logical replication message from the pgoutput wire format.
+    ///
+    /// Borrowed fields (`content` and tuple values) point into `buf`, so no payload is copied.
+    ///
+    /// `buf` must hold exactly one message: bytes left after it are reported as
+    /// [`ParseError::TrailingBytes`], as they mean the message was framed incorrectly. Use
+    /// [`ReplicationMessage::parse_prefix`] to parse a message followed by others.
+    ///
+    /// This is the same as [`ReplicationMessage::parse_with_version`] with [`ProtocolVersion::V1`].
+    pub fn parse(buf: &'a [u8]) -> Result<ReplicationMessage<'a>, ParseError> {
+        Self::parse_with_version(buf, ProtocolVersion::V1)
+    }
+
+    /// Parses a logical replication message sent with the given protocol `version`.
+    ///
+    /// Under [`ProtocolVersion::V2`] and later the leading xid of `Generic`, `Relation`, `Type`,
+    /// `Insert`, `Update`, `Delete` and `Truncate` messages is read into `transaction_id`.
+    ///
+    /// NOTE: Postgres only sends that xid for messages inside a streamed transaction
+    /// (between `StreamStart` and `StreamStop`), so messages outside of it should be parsed
+    /// with [`ProtocolVersion::V1`].
+    pub fn parse_with_version(
+        buf: &'a [u8],
+        version: ProtocolVersion,
+    ) -> Result<ReplicationMessage<'a>, ParseError> {
+        Self::parse_with_options(
+            buf,
+            ParseOptions {
+                version,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Parses the message at the start of `buf`, returning it with the number of bytes consumed.
+    ///
+    /// Bytes after the message are left untouched, so the next message starts at the returned
+    /// offset.
+    ///
+    /// This is the same as [`ReplicationMessage::parse_prefix_with_version`] with
+    /// [`ProtocolVersion::V1`].
+    pub fn parse_prefix(buf: &'a [u8]) -> Result<(ReplicationMessage<'a>, usize), ParseError> {
+        Self::parse_prefix_with_version(buf, ProtocolVersion::V1)
+    }
+
+    /// Parses the message at the start of `buf` sent with the given protocol `version`,
+    /// returning it with the number of bytes consumed.
+    pub fn parse_prefix_with_version(
+        buf: &'a [u8],
+        version: ProtocolVersion,
+    ) -> Result<(ReplicationMessage<'a>, usize), ParseError> {
+        Self::parse_prefix_with_options(
+            buf,
+            ParseOptions {
+                version,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Parses a logical replication message like [`ReplicationMessage::parse_with_version`],
+    /// but returns [`ReplicationMessage::Unknown`] instead of
+    /// [`ParseError::UnknownMessageType`] for message types this crate doesn't know.
+    ///
+    /// This lets consumers skip messages added by newer Postgres versions. Bytes after the
+    /// message, e.g. fields added to it by a newer version, are ignored as well. It's the same
+    /// as [`ReplicationMessage::parse_with_options`] with [`ParseOptions::strict`] unset.
+    pub fn parse_tolerant(
+        buf: &'a [u8],
+        version: ProtocolVersion,
+    ) -> Result<ReplicationMessage<'a>, ParseError> {
+        Self::parse_with_options(
+            buf,
+            ParseOptions {
+                strict: false,
+                version,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    /// Parses a logical replication message with the given [`ParseOptions`].
+    ///
+    /// In [`ParseOptions::strict`] mode, bytes left after the message are reported as
+    /// [`ParseError::TrailingBytes`].
+    pub fn parse_with_options(
+        buf: &'a [u8],
+        options: ParseOptions,
+    ) -> Result<ReplicationMessage<'a>, ParseError> {
+        let (message, consumed) = Self::parse_prefix_with_options(buf, options)?;
+        if options.strict && consumed < buf.len() {
+            return Err(ParseError::TrailingBytes {
+                offset: consumed,
+                count: buf.len() - consumed,
+            });
+        }
+
+        Ok(message)
+    }
+
+    /// Parses the message at the start of `buf` with the given [`ParseOptions`], returning it
+    /// with the number of bytes consumed.
+    ///
+    /// NOTE: [`ReplicationMessage::Unknown`] consumes the whole buffer, as the length of
+    /// unknown messages can't be told.
+    pub fn parse_prefix_with_options(
+        buf: &'a [u8],
+        options: ParseOptions,
+    ) -> Result<(ReplicationMessage<'a>, usize), ParseError> {
+        Self::read_message(Reader::with_options(buf, options))
+    }
+
+    /// Parses the message at the start of `buf`, reading the leading xid of data messages only
+    /// if `in_stream` (and the protocol version carries it).
+    pub(crate) fn parse_prefix_in_stream(
+        buf: &'a [u8],
+        options: ParseOptions,
+        in_stream: bool,
+    ) -> Result<(ReplicationMessage<'a>, usize), ParseError> {
+        let mut reader = Reader::with_options(buf, options);
+        reader.streamed_xid &= in_stream;
+
+        Self::read_message(reader)
+    }
+
+    fn read_message(mut reader: Reader<'a>) -> Result<(ReplicationMessage<'a>, usize), ParseError> {
+        let options = reader.options;
+
+        let message = match reader.read_u8()? {
+            b'B' => Self::Begin(BeginMessage::read_from(&mut reader)?),
+            b'M' => Self::Generic(GenericMessage::read_from(&mut reader)?),
+            b'C' => Self::Commit(CommitMessage::read_from(&mut reader)?),
+            b'O' => Self::Origin(OriginMessage::read_from(&mut reader)?),
+            b'R' => Self::Relation(RelationMessage::read_from(&mut reader)?),
+            b'Y' => Self::Type(TypeMessage::read_from(&mut reader)?),
+            b'I' => Self::Insert(InsertMessage::read_from(&mut reader)?),
+            b'U' => Self::Update(UpdateMessage::read_from(&mut reader)?),
+            b'D' => Self::Delete(DeleteMessage::read_from(&mut reader)?),
+            b'T' => Self::Truncate(TruncateMessage::read_from(&mut reader)?),
+            b'S' => Self::StreamStart(StreamStartMessage::read_from(&mut reader)?),
+            b'E' => Self::StreamStop(StreamStopMessage {}),
+            b'c' => Self::StreamCommit(StreamCommitMessage::read_from(&mut reader)?),
+            b'A' => Self::StreamAbort(StreamAbortMessage::read_from(&mut reader)?),
+            b'b' => Self::BeginPrepare(BeginPrepareMessage::read_from(&mut reader)?),
+            b'P' => Self::Prepare(PrepareMessage::read_from(&mut reader)?),
+            b'K' => Self::CommitPrepared(CommitPreparedMessage::read_from(&mut reader)?),
+            b'r' => Self::RollbackPrepared(RollbackPreparedMessage::read_from(&mut reader)?),
+            b'p' => Self::StreamPrepare(StreamPrepareMessage::read_from(&mut reader)?),
+            tag if !options.strict => Self::Unknown {
+                tag,
+                payload: reader.read_rest(),
+            },
+            tag => return Err(ParseError::UnknownMessageType(tag)),
+        };
+
+        Ok((message, reader.position()))
+    }
+
+    /// Computes the length of the message at the start of `buf` without decoding it, e.g. to
+    /// step over messages of relations a consumer doesn't care about.
+    ///
+    /// This is the same as [`ReplicationMessage::skip_with_options`] with the default options.
+    pub fn skip(buf: &[u8]) -> Result<usize, ParseError> {
+        Self::skip_with_options(buf, ParseOptions::default())
+    }
+
+    /// Computes the length of the message at the start of `buf` sent with the given
+    /// [`ParseOptions`], i.e. the number of bytes
+    /// [`ReplicationMessage::parse_prefix_with_options`] would consume.
+    ///
+    /// Only tags, lengths and NUL terminators are read, nothing is allocated or copied. Values
+    /// themselves aren't checked, so a message that can be skipped may still fail to parse,
+    /// e.g. with [`ParseError::InvalidUtf8`] or [`ParseError::InvalidTimestamp`].
+    pub fn skip_with_options(buf: &[u8], options: ParseOptions) -> Result<usize, ParseError> {
+        Self::skip_message(Reader::with_options(buf, options))
+    }
+
+    /// Computes the length of the message at the start of `buf` like
+    /// [`ReplicationMessage::parse_prefix_in_stream`] would consume.
+    pub(crate) fn skip_in_stream(
+        buf: &[u8],
+        options: ParseOptions,
+        in_stream: bool,
+    ) -> Result<usize, ParseError> {
+        let mut reader = Reader::with_options(buf, options);
+        reader.streamed_xid &= in_stream;
+
+        Self::skip_message(reader)
+    }
+
+    fn skip_message(mut reader: Reader) -> Result<usize, ParseError> {
+        let options = reader.options;
+
+        match reader.read_u8()? {
+            b'B' => reader.skip(8 + 8 + 4)?,
+            b'M' => {
+                reader.read_streamed_xid()?;
+                reader.skip(1 + 8)?;
+                reader.skip_cstr()?;
+                reader.read_length_prefixed()?;
+            }
+            b'C' => reader.skip(1 + 8 + 8 + 8)?,
+            b'O' => {
+                reader.skip(8)?;
+                reader.skip_cstr()?;
+            }
+            b'R' => {
+                reader.read_streamed_xid()?;
+                reader.skip(4)?;
+                reader.skip_cstr()?;
+                reader.skip_cstr()?;
+                reader.skip(1)?;
+                for _ in 0..reader.read_count()? {
+                    reader.skip(1)?;
+                    reader.skip_cstr()?;
+                    reader.skip(4 + 4)?;
+                }
+            }
+            b'Y' => {
+                reader.read_streamed_xid()?;
+                reader.skip(4)?;
+                reader.skip_cstr()?;
+                reader.skip_cstr()?;
+            }
+            b'I' => {
+                reader.read_streamed_xid()?;
+                reader.skip(4)?;
+                match reader.read_tag()? {
+                    (_, b'N') => TupleData::skip(&mut reader)?,
+                    (offset, tag) => return Err(ParseError::InvalidTupleType { offset, tag }),
+                }
+            }
+            b'U' => {
+                reader.read_streamed_xid()?;
+                reader.skip(4)?;
+                let mut tag = reader.read_tag()?;
+                if matches!(tag.1, b'K' | b'O') {
+                    TupleData::skip(&mut reader)?;
+                    tag = reader.read_tag()?;
+                }
+                match tag {
+                    (_, b'N') => TupleData::skip(&mut reader)?,
+                    (offset, tag) => return Err(ParseError::InvalidTupleType { offset, tag }),
+                }
+            }
+            b'D' => {
+                reader.read_streamed_xid()?;
+                reader.skip(4)?;
+                match reader.read_tag()? {
+                    (_, b'K' | b'O') => TupleData::skip(&mut reader)?,
+                    (offset, tag) => return Err(ParseError::InvalidTupleType { offset, tag }),
+                }
+            }
+            b'T' => {
+                reader.read_streamed_xid()?;
+                let offset = reader.position();
+                let length = reader.read_i32()?;
+                let relations_count = usize::try_from(length)
+                    .map_err(|_| ParseError::InvalidLength { offset, length })?;
+                reader.skip(1)?;
+                reader.skip(relations_count.saturating_mul(4))?;
+            }
+            b'S' => reader.skip(4 + 1)?,
+            b'E' => {}
+            b'c' => reader.skip(4 + 1 + 8 + 8 + 8)?,
+            b'A' => {
+                reader.skip(4 + 4)?;
+                if StreamAbortMessage::has_parallel_fields(&reader) {
+                    reader.skip(StreamAbortMessage::PARALLEL_FIELDS_LEN)?;
+                }
+            }
+            b'b' => {
+                reader.skip(8 + 8 + 8 + 4)?;
+                reader.skip_cstr()?;
+            }
+            b'P' | b'K' | b'p' => {
+                reader.skip(1 + 8 + 8 + 8 + 4)?;
+                reader.skip_cstr()?;
+            }
+            b'r' => {
+                reader.skip(1 + 8 + 8 + 8 + 8 + 4)?;
+                reader.skip_cstr()?;
+            }
+            _ if !options.strict => {
+                reader.read_rest();
+            }
+            tag => return Err(ParseError::UnknownMessageType(tag)),
+        }
+
+        Ok(reader.position())
+    }
+}
+
+/// A [`ByteReader`] over a message buffer that knows how the message was sent.
+pub(crate) struct Reader<'a> {
+    bytes: ByteReader<'a>,
+    options: ParseOptions,
+    /// Whether data messages start with the xid of a streamed transaction.
+    streamed_xid: bool,
+}
+
+impl<'a> Deref for Reader<'a> {
+    type Target = ByteReader<'a>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.bytes
+    }
+}
+
+impl DerefMut for Reader<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.bytes
+    }
+}
+
+impl<'a> Reader<'a> {
+    pub(crate) fn new(buf: &'a [u8], version: ProtocolVersion) -> Self {
+        Self::with_options(
+            buf,
+            ParseOptions {
+                version,
+                ..ParseOptions::default()
+            },
+        )
+    }
+
+    pub(crate) fn with_options(buf: &'a [u8], options: ParseOptions) -> Self {
+        Self {
+            bytes: ByteReader::new(buf),
+            options,
+            streamed_xid: options.version.has_streamed_xid(),
+        }
+    }
+
+    /// Reads the xid of a streamed transaction if the protocol version carries one.
+    fn read_streamed_xid(&mut self) -> Result<Option<Xid>, ParseError> {
+        if self.streamed_xid {
+            self.read_xid().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub(crate) fn read_bool(&mut self) -> Result<bool, ParseError> {
+        Ok(self.read_u8()? != 0)
+    }
+
+    /// Reads an Int64 timestamp counted in microseconds since the Postgres epoch.
+    pub(crate) fn read_timestamp(&mut self) -> Result<DateTime<Utc>, ParseError> {
+        let offset = self.position();
+        let micros = self.read_i64()?;
+
+        timestamp::from_pg_micros(micros).ok_or(ParseError::InvalidTimestamp { offset, micros })
+    }
+
+    /// Reads a NUL-terminated string.
+    fn read_string(&mut self) -> Result<String, ParseError> {
+        self.read_cstr().map(str::to_owned)
+    }
+
+    /// Reads the NUL-terminated GID of a prepared transaction, checking its length.
+    fn read_gid(&mut self) -> Result<String, ParseError> {
+        let offset = self.position();
+        let gid = self.read_string()?;
+        validate_gid(&gid).map_err(|err| ParseError::GidTooLong {
+            offset,
+            length: err.length,
+        })?;
+
+        Ok(gid)
+    }
+
+    /// Reads an Int32 length followed by that many bytes, which can't be NULL.
+    fn read_length_prefixed(&mut self) -> Result<&'a [u8], ParseError> {
+        let offset = self.position();
+        self.read_nullable_bytes()?
+            .ok_or(ParseError::InvalidLength { offset, length: -1 })
+    }
+
+    /// Reads an Int16 number of columns, which can't be negative.
+    fn read_count(&mut self) -> Result<usize, ParseError> {
+        let offset = self.position();
+        let count = self.read_i16()?;
+
+        usize::try_from(count).map_err(|_| ParseError::InvalidLength {
+            offset,
+            length: count.into(),
+        })
+    }
+
+    /// Reads `count` items with `read`, into a vector sized up front for as many of them as the
+    /// rest of the buffer can hold, given each takes at least `min_len` bytes.
+    fn read_items<T>(
+        &mut self,
+        count: usize,
+        min_len: usize,
+        mut read: impl FnMut(&mut Self) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = Vec::with_capacity(count.min(self.remaining() / min_len));
+        for _ in 0..count {
+            items.push(read(self)?);
+        }
+
+        Ok(items)
+    }
+
+    /// Reads a tag byte, returning it together with its offset.
+    fn read_tag(&mut self) -> Result<(usize, u8), ParseError> {
+        let offset = self.position();
+
+        Ok((offset, self.read_u8()?))
+    }
+}
+
+impl BeginMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            final_lsn: reader.read_lsn()?,
+            timestamp: reader.read_timestamp()?,
+            transaction_id: reader.read_xid()?,
+        })
+    }
+}
+
+impl<'a> GenericMessage<'a> {
+    fn read_from(reader: &mut Reader<'a>) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_streamed_xid()?;
+        // Flags, the lowest bit marks transactional messages.
+        let is_transactional = reader.read_u8()? & 1 != 0;
+        let lsn = reader.read_lsn()?;
+        let prefix = reader.read_string()?;
+        // A short buffer is reported as `UnexpectedEof`, the content is never cut off.
+        let content = reader.read_length_prefixed()?;
+
+        Ok(Self {
+            transaction_id,
+            is_transactional,
+            lsn,
+            prefix,
+            length: content.len() as i32,
+            content,
+        })
+    }
+}
+
+impl CommitMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        // Flags, currently unused.
+        reader.read_u8()?;
+
+        Ok(Self {
+            lsn: reader.read_lsn()?,
+            final_lsn: reader.read_lsn()?,
+            timestamp: reader.read_timestamp()?,
+        })
+    }
+}
+
+impl OriginMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            lsn: reader.read_lsn()?,
+            name: reader.read_string()?,
+        })
+    }
+}
+
+impl RelationMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_streamed_xid()?;
+        let oid = reader.read_i32()?;
+        let namespace = reader.read_string()?;
+        let name = reader.read_string()?;
+        let (offset, byte) = reader.read_tag()?;
+        let replica_identity = ReplicaIdentity::from_byte(byte)
+            .ok_or(ParseError::InvalidReplicaIdentity { offset, byte })?;
+        let columns_count = reader.read_count()?;
+        let columns = reader.read_items(
+            columns_count,
+            RelationMessageColumn::MIN_LEN,
+            RelationMessageColumn::read_from,
+        )?;
+
+        Ok(Self {
+            transaction_id,
+            oid,
+            namespace: (!namespace.is_empty()).then_some(namespace),
+            name,
+            replica_identity,
+            columns,
+        })
+    }
+}
+
+impl RelationMessageColumn {
+    /// Length of a column with an empty name: flags, NUL, type OID and modifier.
+    const MIN_LEN: usize = 1 + 1 + 4 + 4;
+
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            is_part_of_the_key: reader.read_i8()? & 1 == 1,
+            name: reader.read_string()?,
+            oid: reader.read_i32()?,
+            type_modifier: reader.read_i32()?,
+        })
+    }
+}
+
+impl TypeMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_streamed_xid()?;
+        let oid = reader.read_i32()?;
+        let namespace = reader.read_string()?;
+        let name = reader.read_string()?;
+
+        Ok(Self {
+            transaction_id,
+            oid,
+            namespace: (!namespace.is_empty()).then_some(namespace),
+            name,
+        })
+    }
+}
+
+impl<'a> InsertMessage<'a> {
+    fn read_from(reader: &mut Reader<'a>) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_streamed_xid()?;
+        let oid = reader.read_i32()?;
+
+        match reader.read_tag()? {
+            (_, b'N') => Ok(Self {
+                transaction_id,
+                oid,
+                data: TupleData::read_from(reader)?,
+            }),
+            (offset, tag) => Err(ParseError::InvalidTupleType { offset, tag }),
+        }
+    }
+}
+
+impl<'a> UpdateMessage<'a> {
+    fn read_from(reader: &mut Reader<'a>) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_streamed_xid()?;
+        let oid = reader.read_i32()?;
+        let mut key = None;
+        let mut old = None;
+
+        let mut tag = reader.read_tag()?;
+        match tag.1 {
+            b'K' => key = Some(TupleData::read_from(reader)?),
+            b'O' => old = Some(TupleData::read_from(reader)?),
+            _ => {}
+        }
+        if key.is_some() || old.is_some() {
+            tag = reader.read_tag()?;
+        }
+
+        match tag {
+            (_, b'N') => Ok(Self {
+                transaction_id,
+                oid,
+                key,
+                old,
+                new: TupleData::read_from(reader)?,
+            }),
+            (offset, tag) => Err(ParseError::InvalidTupleType { offset, tag }),
+        }
+    }
+}
+
+impl<'a> DeleteMessage<'a> {
+    fn read_from(reader: &mut Reader<'a>) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_streamed_xid()?;
+        let oid = reader.read_i32()?;
+        let mut key = None;
+        let mut old = None;
+
+        match reader.read_tag()? {
+            (_, b'K') => key = Some(TupleData::read_from(reader)?),
+            (_, b'O') => old = Some(TupleData::read_from(reader)?),
+            (offset, tag) => return Err(ParseError::InvalidTupleType { offset, tag }),
+        }
+
+        Ok(Self {
+            transaction_id,
+            oid,
+            key,
+            old,
+        })
+    }
+}
+
+impl TruncateMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_streamed_xid()?;
+        let offset = reader.position();
+        let length = reader.read_i32()?;
+        let relations_count =
+            usize::try_from(length).map_err(|_| ParseError::InvalidLength { offset, length })?;
+        let options = TruncateOptions::from_bits(reader.read_u8()?);
+        let oids = reader.read_items(relations_count, 4, |reader| reader.read_i32())?;
+
+        Ok(Self {
+            transaction_id,
+            is_cascade: options.cascade,
+            is_restart_identity: options.restart_identity,
+            oids,
+        })
+    }
+}
+
+impl StreamStartMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            transaction_id: Some(reader.read_xid()?),
+            is_first_segment: reader.read_i8()? == 1,
+        })
+    }
+}
+
+impl StreamCommitMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_xid()?;
+        // Flags, currently unused.
+        reader.read_u8()?;
+
+        Ok(Self {
+            transaction_id,
+            lsn: reader.read_lsn()?,
+            final_lsn: reader.read_lsn()?,
+            timestamp: reader.read_timestamp()?,
+        })
+    }
+}
+
+impl StreamAbortMessage {
+    /// Length of the abort LSN and timestamp sent with parallel streaming.
+    const PARALLEL_FIELDS_LEN: usize = 16;
+
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        let transaction_id = reader.read_xid()?;
+        let subtransaction_id = reader.read_xid()?;
+
+        let (abort_lsn, abort_timestamp) = if Self::has_parallel_fields(reader) {
+            (Some(reader.read_lsn()?), Some(reader.read_timestamp()?))
+        } else {
+            (None, None)
+        };
+
+        Ok(Self {
+            transaction_id,
+            subtransaction_id,
+            abort_lsn,
+            abort_timestamp,
+        })
+    }
+
+    /// Whether the abort LSN and timestamp follow the xids.
+    ///
+    /// They are only sent with `streaming = parallel`, as told by
+    /// [`ParseOptions::parallel_streaming`]. In tolerant mode a truncated tail is ignored.
+    fn has_parallel_fields(reader: &Reader) -> bool {
+        let options = reader.options;
+
+        options.parallel_streaming
+            && options.version >= ProtocolVersion::V4
+            && (options.strict || reader.remaining() >= Self::PARALLEL_FIELDS_LEN)
+    }
+}
+
+impl BeginPrepareMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        Ok(Self {
+            lsn: reader.read_lsn()?,
+            final_lsn: reader.read_lsn()?,
+            timestamp: reader.read_timestamp()?,
+            transaction_id: reader.read_xid()?,
+            gid: reader.read_gid()?,
+        })
+    }
+}
+
+impl PrepareMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        // Flags, currently unused.
+        reader.read_u8()?;
+
+        Ok(Self {
+            lsn: reader.read_lsn()?,
+            final_lsn: reader.read_lsn()?,
+            timestamp: reader.read_timestamp()?,
+            transaction_id: reader.read_xid()?,
+            gid: reader.read_gid()?,
+        })
+    }
+}
+
+impl CommitPreparedMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        // Flags, currently unused.
+        reader.read_u8()?;
+
+        Ok(Self {
+            lsn: reader.read_lsn()?,
+            final_lsn: reader.read_lsn()?,
+            timestamp: reader.read_timestamp()?,
+            transaction_id: reader.read_xid()?,
+            gid: reader.read_gid()?,
+        })
+    }
+}
+
+impl RollbackPreparedMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        // Flags, currently unused.
+        reader.read_u8()?;
+
+        Ok(Self {
+            lsn: reader.read_lsn()?,
+            final_lsn: reader.read_lsn()?,
+            prepare_timestamp: reader.read_timestamp()?,
+            timestamp: reader.read_timestamp()?,
+            transaction_id: reader.read_xid()?,
+            gid: reader.read_gid()?,
+        })
+    }
+}
+
+impl StreamPrepareMessage {
+    fn read_from(reader: &mut Reader) -> Result<Self, ParseError> {
+        // Flags, currently unused.
+        reader.read_u8()?;
+
+        Ok(Self {
+            lsn: reader.read_lsn()?,
+            final_lsn: reader.read_lsn()?,
+            timestamp: reader.read_timestamp()?,
+            transaction_id: reader.read_xid()?,
+            gid: reader.read_gid()?,
+        })
+    }
+}
+
+impl<'a> TupleData<'a> {
+    fn read_from(reader: &mut Reader<'a>) -> Result<Self, ParseError> {
+        // Every column takes at least its kind byte.
+        let columns_count = reader.read_count()?;
+        let columns = reader.read_items(columns_count, 1, TupleDataColumn::read_from)?;
+
+        Ok(Self { columns })
+    }
+
+    /// Advances past a tuple without decoding its columns.
+    fn skip(reader: &mut Reader) -> Result<(), ParseError> {
+        for _ in 0..reader.read_count()? {
+            match reader.read_tag()? {
+                (_, b'n' | b'u') => {}
+                (_, b't' | b'b') => {
+                    reader.read_nullable_bytes()?;
+                }
+                (offset, kind) => return Err(ParseError::InvalidTupleColumnKind { offset, kind }),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<'a> TupleDataColumn<'a> {
+    fn read_from(reader: &mut Reader<'a>) -> Result<Self, ParseError> {
+        let kind = match reader.read_tag()? {
+            (_, b'n') => TupleDataKind::Null,
+            (_, b'u') => TupleDataKind::UnchangedToast,
+            // A length of -1 is how other parts of the protocol send NULL; it's never sliced.
+            (_, b't') => {
+                let offset = reader.position() + 4;
+                match reader.read_nullable_bytes()? {
+                    Some(bytes) if reader.options.lossy_utf8 => {
+                        TupleDataKind::Text(String::from_utf8_lossy(bytes))
+                    }
+                    Some(bytes) => TupleDataKind::Text(Cow::Borrowed(utf8(bytes, offset)?)),
+                    None => TupleDataKind::Null,
+                }
+            }
+            (_, b'b') => reader
+                .read_nullable_bytes()?
+                .map_or(TupleDataKind::Null, TupleDataKind::Binary),
+            (offset, kind) => return Err(ParseError::InvalidTupleColumnKind { offset, kind }),
+        };
+
+        Ok(Self { kind })
+    }
+}
@@ -0,0 +1,30 @@
+//! Re-exports of the commonly used types and traits.
+//!
+//! ```
+//! use postgres_replication_types::prelude::*;
+//!
+//! let insert = InsertMessage::builder()
+//!     .oid(16385)
+//!     .data(TupleData::builder().text("42").null().build())
+//!     .build();
+//!
+//! let mut buf = Vec::new();
+//! ReplicationMessage::Insert(insert).encode(&mut buf)?;
+//!
+//! let ReplicationMessage::Insert(insert) = ReplicationMessage::parse(&buf)? else {
+//!     unreachable!();
+//! };
+//! let event = ChangeEvent::from(insert);
+//! assert_eq!(event.op, Op::Insert);
+//! assert_eq!(event.after.unwrap().columns[0].as_i32()?, 42);
+//! # Ok::<(), Box<dyn std::error::Error>>(())
+//! ```
+
+pub use crate::{
+    BeginMessage, ChangeEvent, CommitMessage, DeleteMessage, FromValue, InsertMessage, Interval,
+    Lsn, MessageIter, MessageType, MessageVisitor, Op, OwnedReplicationMessage, ParseError,
+    ParseOptions, ProtocolVersion, RelationCache, RelationKey, RelationMessage,
+    RelationMessageColumn, ReplicaIdentity, ReplicationMessage, ReplicationStream, TruncateEvent,
+    TruncateMessage, TupleData, TupleDataColumn, TupleDataKind, UpdateMessage, ValueDecodeError,
+    Xid,
+};
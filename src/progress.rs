@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+
+use crate::{CopyData, Lsn, StandbyStatusUpdate};
+
+/// Tracks how far a consumer has received and applied the WAL, to report replication lag and
+/// build [`StandbyStatusUpdate`]s.
+///
+/// Every [`CopyData`] frame received from the server is observed, and the LSN of every
+/// transaction the consumer is done with (e.g. the `end_lsn` of its commit) is marked applied.
+/// Locations never move backwards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReplicationProgress {
+    last_received: Lsn,
+    last_applied: Lsn,
+    server_wal_end: Lsn,
+}
+
+impl ReplicationProgress {
+    /// Creates a tracker that hasn't received anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a tracker resuming from a location that was already applied, e.g. the
+    /// `confirmed_flush_lsn` of the replication slot.
+    pub fn starting_at(lsn: Lsn) -> Self {
+        Self {
+            last_received: lsn,
+            last_applied: lsn,
+            server_wal_end: lsn,
+        }
+    }
+
+    /// Accepts a frame received from the server.
+    pub fn observe(&mut self, frame: &CopyData<'_>) {
+        match frame {
+            // The payload is a decoded pgoutput message rather than raw WAL, so its length
+            // says nothing about LSNs; like pg_recvlogical, only its start counts as received.
+            CopyData::XLogData(data) => {
+                self.last_received = self.last_received.max(data.wal_start);
+                self.server_wal_end = self.server_wal_end.max(data.wal_end);
+            }
+            CopyData::PrimaryKeepalive(keepalive) => {
+                self.server_wal_end = self.server_wal_end.max(keepalive.wal_end);
+            }
+        }
+    }
+
+    /// Marks the WAL up to `lsn` as applied by the consumer.
+    pub fn applied(&mut self, lsn: Lsn) {
+        self.last_applied = self.last_applied.max(lsn);
+        self.last_received = self.last_received.max(lsn);
+    }
+
+    /// The start of the last WAL data received.
+    pub fn last_received(&self) -> Lsn {
+        self.last_received
+    }
+
+    /// The end of the WAL applied by the consumer.
+    pub fn last_applied(&self) -> Lsn {
+        self.last_applied
+    }
+
+    /// The latest end of WAL reported by the server.
+    pub fn server_wal_end(&self) -> Lsn {
+        self.server_wal_end
+    }
+
+    /// Number of bytes of WAL the consumer has yet to apply, counted up to the end of WAL
+    /// reported by the server or received, whichever is further.
+    pub fn lag_bytes(&self) -> u64 {
+        self.server_wal_end
+            .max(self.last_received)
+            .saturating_sub(self.last_applied)
+    }
+
+    /// Builds the status update reporting this progress to the server: written up to what was
+    /// received, and flushed and applied up to what was applied.
+    pub fn status_update(
+        &self,
+        clock: DateTime<Utc>,
+        reply_requested: bool,
+    ) -> StandbyStatusUpdate {
+        StandbyStatusUpdate {
+            written_lsn: self.last_received,
+            flushed_lsn: self.last_applied,
+            applied_lsn: self.last_applied,
+            clock,
+            reply_requested,
+        }
+    }
+}
@@ -0,0 +1,161 @@
+use alloc::string::String;
+use core::ops::Bound;
+
+use crate::array::BinaryReader;
+use crate::value::FromValue;
+use crate::{TupleDataColumn, ValueDecodeError};
+
+const EMPTY: u8 = 0x01;
+const LOWER_INCLUSIVE: u8 = 0x02;
+const UPPER_INCLUSIVE: u8 = 0x04;
+const LOWER_INFINITE: u8 = 0x08;
+const UPPER_INFINITE: u8 = 0x10;
+
+/// A value of a Postgres range type, such as `int4range` or `tsrange`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum PgRange<T> {
+    /// The range doesn't contain any value.
+    Empty,
+    /// The range between two bounds, either of which can be unbounded.
+    Range {
+        /// The lower bound.
+        lower: Bound<T>,
+        /// The upper bound.
+        upper: Bound<T>,
+    },
+}
+
+impl TupleDataColumn<'_> {
+    /// Decodes a value of a range type whose elements are of type `T`, e.g. `PgRange<i32>`
+    /// for `int4range`.
+    ///
+    /// Text values are expected as Postgres prints them, e.g. `[1,10)`, `(,5]` or `empty`.
+    pub fn as_range<T: FromValue>(&self) -> Result<PgRange<T>, ValueDecodeError> {
+        self.value()
+    }
+}
+
+fn invalid() -> ValueDecodeError {
+    ValueDecodeError::InvalidValue { type_name: "range" }
+}
+
+impl<T: FromValue> FromValue for PgRange<T> {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        if text.trim().eq_ignore_ascii_case("empty") {
+            return Ok(Self::Empty);
+        }
+
+        let text = text.trim();
+        let lower_inclusive = match text.chars().next() {
+            Some('[') => true,
+            Some('(') => false,
+            _ => return Err(invalid()),
+        };
+        let upper_inclusive = match text.chars().last() {
+            Some(']') => true,
+            Some(')') => false,
+            _ => return Err(invalid()),
+        };
+        let inner = text.get(1..text.len() - 1).ok_or_else(invalid)?;
+
+        let (lower, rest) = read_bound(inner)?;
+        let rest = rest.strip_prefix(',').ok_or_else(invalid)?;
+        let (upper, rest) = read_bound(rest)?;
+        if !rest.is_empty() {
+            return Err(invalid());
+        }
+
+        let bound = |value: Option<String>, inclusive| -> Result<Bound<T>, ValueDecodeError> {
+            Ok(match value {
+                None => Bound::Unbounded,
+                Some(value) if inclusive => Bound::Included(T::from_text(&value)?),
+                Some(value) => Bound::Excluded(T::from_text(&value)?),
+            })
+        };
+
+        Ok(Self::Range {
+            lower: bound(lower, lower_inclusive)?,
+            upper: bound(upper, upper_inclusive)?,
+        })
+    }
+
+    /// Binary values are a flags byte, followed by the length-prefixed binary values of the
+    /// bounds that are present.
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        let (&flags, bytes) = bytes.split_first().ok_or_else(invalid)?;
+        if flags & EMPTY != 0 {
+            return if bytes.is_empty() {
+                Ok(Self::Empty)
+            } else {
+                Err(invalid())
+            };
+        }
+
+        let mut reader = BinaryReader::new(bytes, "range");
+        let mut bound = |infinite, inclusive| -> Result<Bound<T>, ValueDecodeError> {
+            if flags & infinite != 0 {
+                return Ok(Bound::Unbounded);
+            }
+            let value = T::from_binary(reader.read_element()?.ok_or_else(invalid)?)?;
+
+            Ok(if flags & inclusive != 0 {
+                Bound::Included(value)
+            } else {
+                Bound::Excluded(value)
+            })
+        };
+        let lower = bound(LOWER_INFINITE, LOWER_INCLUSIVE)?;
+        let upper = bound(UPPER_INFINITE, UPPER_INCLUSIVE)?;
+        if !reader.bytes.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Self::Range { lower, upper })
+    }
+}
+
+/// Reads a bound of the text format up to the next `,` or the end, returning `None` for an
+/// unbounded side, along with the rest of the text.
+fn read_bound(text: &str) -> Result<(Option<String>, &str), ValueDecodeError> {
+    let mut bound = String::new();
+    let mut quoted = false;
+    // An empty quoted bound is an empty string rather than an unbounded side.
+    let mut was_quoted = false;
+    let mut chars = text.char_indices().peekable();
+
+    while let Some(&(i, c)) = chars.peek() {
+        match c {
+            ',' if !quoted => {
+                return Ok((
+                    (!bound.is_empty() || was_quoted).then_some(bound),
+                    &text[i..],
+                ))
+            }
+            '"' => {
+                chars.next();
+                // A doubled quote inside a quoted bound stands for a quote.
+                if quoted && chars.next_if(|&(_, c)| c == '"').is_some() {
+                    bound.push('"');
+                } else {
+                    quoted = !quoted;
+                    was_quoted = true;
+                }
+                continue;
+            }
+            '\\' => {
+                chars.next();
+                bound.push(chars.next().ok_or_else(invalid)?.1);
+                continue;
+            }
+            c => bound.push(c),
+        }
+        chars.next();
+    }
+
+    if quoted {
+        return Err(invalid());
+    }
+    Ok(((!bound.is_empty() || was_quoted).then_some(bound), ""))
+}
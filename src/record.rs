@@ -0,0 +1,62 @@
+use alloc::vec::Vec;
+
+use crate::array::BinaryReader;
+use crate::{TupleDataColumn, TupleDataKind, ValueDecodeError};
+
+/// A field of a composite (`record`) value, as decoded by [`TupleDataColumn::as_record`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RecordField<'a> {
+    /// OID of the type of the field.
+    pub type_oid: i32,
+    /// The binary value of the field, `None` for NULL.
+    pub value: Option<&'a [u8]>,
+}
+
+impl<'a> RecordField<'a> {
+    /// The field as a column, so that its value can be decoded with the `as_*` methods of
+    /// [`TupleDataColumn`], including [`TupleDataColumn::as_record`] for nested records.
+    pub fn column(&self) -> TupleDataColumn<'a> {
+        let kind = match self.value {
+            Some(bytes) => TupleDataKind::Binary(bytes),
+            None => TupleDataKind::Null,
+        };
+
+        TupleDataColumn { kind }
+    }
+}
+
+impl<'a> TupleDataColumn<'a> {
+    /// Splits a binary composite value into its fields.
+    ///
+    /// The binary format is the number of fields, followed by the type OID and the
+    /// length-prefixed binary value of every field. Field values of composite types are records
+    /// themselves. Text values (e.g. `(1,abc)`) don't carry the field types, so they are rejected.
+    pub fn as_record(&self) -> Result<Vec<RecordField<'a>>, ValueDecodeError> {
+        let bytes = match self.kind {
+            TupleDataKind::Null => return Err(ValueDecodeError::Null),
+            TupleDataKind::UnchangedToast => return Err(ValueDecodeError::UnchangedToast),
+            TupleDataKind::Text(_) => {
+                return Err(ValueDecodeError::WrongFormat {
+                    type_name: "record",
+                })
+            }
+            TupleDataKind::Binary(bytes) => bytes,
+        };
+
+        let mut reader = BinaryReader::new(bytes, "record");
+        let count = usize::try_from(reader.read_i32()?).map_err(|_| reader.invalid())?;
+        let fields = (0..count)
+            .map(|_| {
+                Ok(RecordField {
+                    type_oid: reader.read_i32()?,
+                    value: reader.read_element()?,
+                })
+            })
+            .collect::<Result<_, ValueDecodeError>>()?;
+        if !reader.bytes.is_empty() {
+            return Err(reader.invalid());
+        }
+
+        Ok(fields)
+    }
+}
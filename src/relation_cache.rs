@@ -0,0 +1,85 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{InsertMessage, RelationMessage, TupleData, TupleDataColumn};
+
+/// A cache of [`RelationMessage`]s, used to resolve column names of DML messages.
+///
+/// Postgres sends a [`RelationMessage`] before the first DML message for a relation (and again
+/// after its definition changes), so every message passing through should be inserted.
+#[derive(Debug, Clone, Default)]
+pub struct RelationCache {
+    relations: BTreeMap<i32, RelationMessage>,
+}
+
+impl RelationCache {
+    /// Creates an empty cache.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stores the relation, returning the previous definition with the same OID.
+    pub fn insert(&mut self, relation: RelationMessage) -> Option<RelationMessage> {
+        self.relations.insert(relation.oid, relation)
+    }
+
+    /// Stores the relation, returning whether it replaced a definition with a different schema
+    /// (see [`RelationMessage::schema_differs`]), e.g. after an `ALTER TABLE`.
+    ///
+    /// Rows decoded against the previous definition should be discarded when it did.
+    pub fn insert_checked(&mut self, relation: RelationMessage) -> bool {
+        let oid = relation.oid;
+        match self.relations.insert(oid, relation) {
+            Some(previous) => previous.schema_differs(&self.relations[&oid]),
+            None => false,
+        }
+    }
+
+    /// Returns the relation with the given OID.
+    pub fn get(&self, oid: i32) -> Option<&RelationMessage> {
+        self.relations.get(&oid)
+    }
+
+    /// Removes the relation with the given OID.
+    pub fn remove(&mut self, oid: i32) -> Option<RelationMessage> {
+        self.relations.remove(&oid)
+    }
+
+    /// Pairs the tuple of an [`InsertMessage`] with the column names of its relation.
+    ///
+    /// Returns `None` if the relation is unknown or its column count doesn't match the tuple.
+    pub fn resolve<'a>(&'a self, message: &'a InsertMessage<'a>) -> Option<NamedRow<'a>> {
+        self.resolve_tuple(message.oid, &message.data)
+    }
+
+    /// Pairs a tuple of the relation with the given OID with its column names.
+    ///
+    /// Returns `None` if the relation is unknown or its column count doesn't match the tuple.
+    pub fn resolve_tuple<'a>(&'a self, oid: i32, tuple: &'a TupleData<'a>) -> Option<NamedRow<'a>> {
+        let relation = self.get(oid)?;
+        if relation.columns.len() != tuple.columns.len() {
+            return None;
+        }
+
+        Some(NamedRow {
+            columns: tuple.iter_named(relation).collect(),
+        })
+    }
+}
+
+/// A tuple with its values paired with the column names of the relation.
+#[derive(Debug, Clone)]
+pub struct NamedRow<'a> {
+    /// Pairs of column name and value, in the order of the relation columns.
+    pub columns: Vec<(&'a str, &'a TupleDataColumn<'a>)>,
+}
+
+impl<'a> NamedRow<'a> {
+    /// Returns the value of the column with the given name.
+    pub fn get(&self, name: &str) -> Option<&'a TupleDataColumn<'a>> {
+        self.columns
+            .iter()
+            .find(|(column, _)| *column == name)
+            .map(|(_, value)| *value)
+    }
+}
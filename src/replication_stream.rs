@@ -0,0 +1,325 @@
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::error::ReplicationStreamError;
+use crate::{
+    ByteReader, MismatchError, Op, OwnedReplicationMessage, OwnedTupleData, OwnedTupleDataColumn,
+    ParseError, ParseOptions, ProtocolVersion, RelationCache, RelationMessage, ReplicationMessage,
+    StreamReassembler,
+};
+
+/// A decoder of a whole replication stream, yielding row changes with their column names.
+///
+/// Every pgoutput message received from the server (the payload of
+/// [`XLogData`](crate::XLogData)) is pushed in order. Relations are cached as their
+/// [`RelationMessage`]s pass through, and streamed transactions are buffered until they're
+/// committed, so changes come out as the server applied them.
+///
+/// Changes are tagged with the origin of their transaction, as sent in an
+/// [`OriginMessage`](crate::OriginMessage) when the transaction was replicated to the server
+/// from elsewhere. This lets bidirectional setups skip changes that originated on their side.
+#[derive(Clone, Default)]
+pub struct ReplicationStream {
+    options: ParseOptions,
+    in_stream: bool,
+    /// Name of the last origin of the current transaction.
+    origin: Option<String>,
+    relations: RelationCache,
+    reassembler: StreamReassembler,
+    /// Whether changes of the relation with the given OID are decoded.
+    relation_filter: Option<Arc<dyn Fn(i32) -> bool + Send + Sync>>,
+}
+
+impl fmt::Debug for ReplicationStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReplicationStream")
+            .field("options", &self.options)
+            .field("in_stream", &self.in_stream)
+            .field("origin", &self.origin)
+            .field("relations", &self.relations)
+            .field("reassembler", &self.reassembler)
+            .finish_non_exhaustive()
+    }
+}
+
+/// A row change with the column names of its relation, yielded by [`ReplicationStream`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NamedChangeEvent {
+    /// The kind of change.
+    pub op: Op,
+    /// OID of the relation.
+    pub relation_oid: i32,
+    /// Namespace of the relation, `None` for `pg_catalog`.
+    pub namespace: Option<String>,
+    /// Name of the relation.
+    pub relation_name: String,
+    /// Name of the last origin seen in the transaction of the change, `None` if the
+    /// transaction originated on the server.
+    pub origin: Option<String>,
+    /// The old row, or only its replica identity columns, like [`ChangeEvent::before`](crate::ChangeEvent::before).
+    pub before: Option<OwnedNamedRow>,
+    /// The new row, `None` for deletes.
+    pub after: Option<OwnedNamedRow>,
+}
+
+/// An owned [`NamedRow`](crate::NamedRow).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OwnedNamedRow {
+    /// Pairs of column name and value, in the order of the relation columns.
+    pub columns: Vec<(String, OwnedTupleDataColumn)>,
+}
+
+impl OwnedNamedRow {
+    /// Returns the value of the column with the given name.
+    pub fn get(&self, name: &str) -> Option<&OwnedTupleDataColumn> {
+        self.columns
+            .iter()
+            .find(|(column, _)| column == name)
+            .map(|(_, value)| value)
+    }
+}
+
+impl ReplicationStream {
+    /// Creates a stream of messages sent with protocol version 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a stream of messages sent with the given protocol `version`.
+    pub fn with_version(version: ProtocolVersion) -> Self {
+        Self::with_options(ParseOptions {
+            version,
+            ..ParseOptions::default()
+        })
+    }
+
+    /// Creates a stream parsing messages with the given [`ParseOptions`].
+    pub fn with_options(options: ParseOptions) -> Self {
+        Self {
+            options,
+            ..Self::default()
+        }
+    }
+
+    /// Only yields changes of the relations whose OID matches `filter`.
+    ///
+    /// Inserts, updates and deletes of other relations are skipped without being decoded.
+    /// Relations are still cached and transactions tracked, so the filter doesn't affect other
+    /// messages.
+    pub fn with_relation_filter(
+        mut self,
+        filter: impl Fn(i32) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.relation_filter = Some(Arc::new(filter));
+        self
+    }
+
+    /// The relations seen so far.
+    pub fn relations(&self) -> &RelationCache {
+        &self.relations
+    }
+
+    /// Accepts the next message, which must fill `buf` exactly in strict mode.
+    ///
+    /// Returns the changes it completes: the change itself for an insert, update or delete
+    /// outside of a streamed transaction, all changes of a streamed transaction for its
+    /// `StreamCommit` or `StreamPrepare`, and none otherwise.
+    pub fn push(&mut self, buf: &[u8]) -> Result<Vec<NamedChangeEvent>, ReplicationStreamError> {
+        if self.is_filtered_out(buf) {
+            let consumed = ReplicationMessage::skip_in_stream(buf, self.options, self.in_stream)?;
+            self.check_consumed(buf, consumed)?;
+            return Ok(Vec::new());
+        }
+
+        let (message, consumed) =
+            ReplicationMessage::parse_prefix_in_stream(buf, self.options, self.in_stream)?;
+        self.check_consumed(buf, consumed)?;
+
+        let mut events = Vec::new();
+        match &message {
+            ReplicationMessage::StreamStart(_) => self.in_stream = true,
+            ReplicationMessage::StreamStop(_) => self.in_stream = false,
+            _ => {}
+        }
+        if self.in_stream || message.is_stream_control() {
+            for message in self.reassembler.push(&message).into_iter().flatten() {
+                self.apply(message, &mut events)?;
+            }
+        } else {
+            self.apply(message.into_owned(), &mut events)?;
+        }
+
+        Ok(events)
+    }
+
+    /// Discards the cached relations and buffered transactions, e.g. after reconnecting to the
+    /// replication slot.
+    pub fn clear(&mut self) {
+        self.in_stream = false;
+        self.origin = None;
+        self.relations = RelationCache::new();
+        self.reassembler.clear();
+    }
+
+    /// Whether `buf` holds an insert, update or delete of a relation the filter rejects.
+    ///
+    /// Malformed messages are never filtered out, so that parsing reports them.
+    fn is_filtered_out(&self, buf: &[u8]) -> bool {
+        let Some(filter) = &self.relation_filter else {
+            return false;
+        };
+
+        let mut reader = ByteReader::new(buf);
+        if !matches!(reader.read_u8(), Ok(b'I' | b'U' | b'D')) {
+            return false;
+        }
+        if self.in_stream && self.options.version.has_streamed_xid() && reader.read_xid().is_err() {
+            return false;
+        }
+
+        reader.read_i32().is_ok_and(|oid| !filter(oid))
+    }
+
+    /// Checks that the message filled `buf` in strict mode.
+    fn check_consumed(&self, buf: &[u8], consumed: usize) -> Result<(), ParseError> {
+        if self.options.strict && consumed < buf.len() {
+            return Err(ParseError::TrailingBytes {
+                offset: consumed,
+                count: buf.len() - consumed,
+            });
+        }
+
+        Ok(())
+    }
+
+    fn apply(
+        &mut self,
+        message: OwnedReplicationMessage,
+        events: &mut Vec<NamedChangeEvent>,
+    ) -> Result<(), ReplicationStreamError> {
+        let (op, oid, before, after) = match message {
+            OwnedReplicationMessage::Relation(relation) => {
+                self.relations.insert(relation);
+                return Ok(());
+            }
+            OwnedReplicationMessage::Origin(origin) => {
+                self.origin = Some(origin.name);
+                return Ok(());
+            }
+            OwnedReplicationMessage::Begin(_)
+            | OwnedReplicationMessage::BeginPrepare(_)
+            | OwnedReplicationMessage::Commit(_)
+            | OwnedReplicationMessage::Prepare(_)
+            | OwnedReplicationMessage::StreamCommit(_)
+            | OwnedReplicationMessage::StreamPrepare(_) => {
+                self.origin = None;
+                return Ok(());
+            }
+            OwnedReplicationMessage::Insert(insert) => {
+                (Op::Insert, insert.oid, None, Some(insert.data))
+            }
+            OwnedReplicationMessage::Update(update) => (
+                Op::Update,
+                update.oid,
+                Before::new(update.old, update.key),
+                Some(update.new),
+            ),
+            OwnedReplicationMessage::Delete(delete) => (
+                Op::Delete,
+                delete.oid,
+                Before::new(delete.old, delete.key),
+                None,
+            ),
+            _ => return Ok(()),
+        };
+
+        let relation = self
+            .relations
+            .get(oid)
+            .ok_or(ReplicationStreamError::UnknownRelation { oid })?;
+        let before = match before {
+            Some(Before::Old(old)) => Some(named(relation, old, false)?),
+            Some(Before::Key(key)) => Some(named(relation, key, true)?),
+            None => None,
+        };
+        let after = after.map(|new| named(relation, new, false)).transpose()?;
+
+        events.push(NamedChangeEvent {
+            op,
+            relation_oid: oid,
+            namespace: relation.namespace.clone(),
+            relation_name: relation.name.clone(),
+            origin: self.origin.clone(),
+            before,
+            after,
+        });
+        Ok(())
+    }
+}
+
+impl ReplicationMessage<'_> {
+    /// Whether the message starts, stops or ends a streamed transaction.
+    fn is_stream_control(&self) -> bool {
+        matches!(
+            self,
+            Self::StreamStart(_)
+                | Self::StreamStop(_)
+                | Self::StreamCommit(_)
+                | Self::StreamAbort(_)
+                | Self::StreamPrepare(_)
+        )
+    }
+}
+
+enum Before {
+    Old(OwnedTupleData),
+    Key(OwnedTupleData),
+}
+
+impl Before {
+    fn new(old: Option<OwnedTupleData>, key: Option<OwnedTupleData>) -> Option<Self> {
+        old.map(Self::Old).or(key.map(Self::Key))
+    }
+}
+
+/// Pairs the tuple with the column names of the relation. A key tuple is paired with the key
+/// columns only, whether it holds all columns (with NULL for the others) or just the key.
+fn named(
+    relation: &RelationMessage,
+    tuple: OwnedTupleData,
+    is_key: bool,
+) -> Result<OwnedNamedRow, MismatchError> {
+    let key_count = relation
+        .columns
+        .iter()
+        .filter(|column| column.is_part_of_the_key)
+        .count();
+    let columns = if tuple.columns.len() == relation.columns.len() {
+        relation
+            .columns
+            .iter()
+            .zip(tuple.columns)
+            .filter(|(column, _)| !is_key || column.is_part_of_the_key)
+            .map(|(column, value)| (column.name.clone(), value))
+            .collect()
+    } else if is_key && tuple.columns.len() == key_count {
+        relation
+            .columns
+            .iter()
+            .filter(|column| column.is_part_of_the_key)
+            .zip(tuple.columns)
+            .map(|(column, value)| (column.name.clone(), value))
+            .collect()
+    } else {
+        return Err(MismatchError {
+            expected: relation.columns.len(),
+            actual: tuple.columns.len(),
+        });
+    };
+
+    Ok(OwnedNamedRow { columns })
+}
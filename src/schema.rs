@@ -0,0 +1,80 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::{RelationMessage, RelationMessageColumn, ReplicaIdentity};
+
+/// Description of a relation column, e.g. for building the schema of a columnar sink.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ColumnSchema {
+    /// Name of the column.
+    pub name: String,
+    /// OID of the column's data type.
+    pub type_oid: i32,
+    /// Type modifier of the column (`atttypmod`).
+    pub type_modifier: i32,
+    /// Is part of the replica identity?
+    pub in_key: bool,
+    /// Can the column hold NULL values?
+    ///
+    /// NOTE: The replication protocol doesn't send `NOT NULL` constraints, so this is only
+    /// `false` for columns known to be non-null: the columns of the primary key or of the
+    /// replica identity index.
+    pub nullable: bool,
+}
+
+impl From<&RelationMessageColumn> for ColumnSchema {
+    /// Converts the column, assuming it's nullable since that depends on its relation.
+    fn from(column: &RelationMessageColumn) -> Self {
+        Self {
+            name: column.name.clone(),
+            type_oid: column.oid,
+            type_modifier: column.type_modifier,
+            in_key: column.is_part_of_the_key,
+            nullable: true,
+        }
+    }
+}
+
+impl From<RelationMessageColumn> for ColumnSchema {
+    /// Converts the column, assuming it's nullable since that depends on its relation.
+    fn from(column: RelationMessageColumn) -> Self {
+        Self {
+            name: column.name,
+            type_oid: column.oid,
+            type_modifier: column.type_modifier,
+            in_key: column.is_part_of_the_key,
+            nullable: true,
+        }
+    }
+}
+
+impl RelationMessage {
+    /// Describes the columns of the relation, in order.
+    ///
+    /// Key columns are non-nullable unless the relation has REPLICA IDENTITY FULL, which flags
+    /// every column as part of the key.
+    pub fn schema(&self) -> Vec<ColumnSchema> {
+        let key_is_not_null = self.replica_identity != ReplicaIdentity::Full;
+
+        self.columns
+            .iter()
+            .map(|column| ColumnSchema {
+                nullable: !(column.is_part_of_the_key && key_is_not_null),
+                ..column.into()
+            })
+            .collect()
+    }
+
+    /// Whether tuples of `other` are laid out differently, i.e. the relations don't have the
+    /// same columns (by name, type and type modifier) in the same order.
+    ///
+    /// Changes of the replica identity alone don't count, as they don't affect how tuples are
+    /// decoded.
+    pub fn schema_differs(&self, other: &RelationMessage) -> bool {
+        self.columns.len() != other.columns.len()
+            || self.columns.iter().zip(&other.columns).any(|(a, b)| {
+                a.name != b.name || a.oid != b.oid || a.type_modifier != b.type_modifier
+            })
+    }
+}
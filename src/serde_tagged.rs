@@ -0,0 +1,157 @@
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::*;
+
+// Human-readable formats like JSON get messages and tuple values tagged with their kind
+// (`{"type": "insert", ...}`), which binary formats like bincode can't deserialize as they don't
+// describe their own structure. Those get the default externally tagged representation.
+macro_rules! representations {
+    (
+        #[serde($($tagging:tt)*)]
+        #[derive($($derive:path),*)]
+        enum $tagged:ident, $compact:ident $(<$lt:lifetime>)? for $remote:literal {
+            $($variants:tt)*
+        }
+    ) => {
+        #[derive($($derive),*)]
+        #[serde(remote = $remote, rename_all = "snake_case", $($tagging)*)]
+        enum $tagged $(<$lt>)? { $($variants)* }
+
+        #[derive($($derive),*)]
+        #[serde(remote = $remote, rename_all = "snake_case")]
+        enum $compact $(<$lt>)? { $($variants)* }
+    };
+}
+
+macro_rules! serialize {
+    ($type:ty, $tagged:ident, $compact:ident) => {
+        impl Serialize for $type {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                if serializer.is_human_readable() {
+                    $tagged::serialize(self, serializer)
+                } else {
+                    $compact::serialize(self, serializer)
+                }
+            }
+        }
+    };
+}
+
+macro_rules! deserialize {
+    ($type:ty, $tagged:ident, $compact:ident) => {
+        impl<'de> Deserialize<'de> for $type {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                if deserializer.is_human_readable() {
+                    $tagged::deserialize(deserializer)
+                } else {
+                    $compact::deserialize(deserializer)
+                }
+            }
+        }
+    };
+}
+
+representations! {
+    #[serde(tag = "type")]
+    #[derive(Serialize)]
+    enum TaggedMessage, CompactMessage<'a> for "ReplicationMessage" {
+        Begin(BeginMessage),
+        Generic(GenericMessage<'a>),
+        Commit(CommitMessage),
+        Origin(OriginMessage),
+        Relation(RelationMessage),
+        Type(TypeMessage),
+        Insert(InsertMessage<'a>),
+        Update(UpdateMessage<'a>),
+        Delete(DeleteMessage<'a>),
+        Truncate(TruncateMessage),
+        StreamStart(StreamStartMessage),
+        StreamStop(StreamStopMessage),
+        StreamCommit(StreamCommitMessage),
+        StreamAbort(StreamAbortMessage),
+        BeginPrepare(BeginPrepareMessage),
+        Prepare(PrepareMessage),
+        CommitPrepared(CommitPreparedMessage),
+        RollbackPrepared(RollbackPreparedMessage),
+        StreamPrepare(StreamPrepareMessage),
+        Unknown {
+            tag: u8,
+            #[serde(with = "crate::serde_base64")]
+            payload: &'a [u8],
+        },
+    }
+}
+
+representations! {
+    #[serde(tag = "type")]
+    #[derive(Serialize, Deserialize)]
+    enum TaggedOwnedMessage, CompactOwnedMessage for "OwnedReplicationMessage" {
+        Begin(BeginMessage),
+        Generic(OwnedGenericMessage),
+        Commit(CommitMessage),
+        Origin(OriginMessage),
+        Relation(RelationMessage),
+        Type(TypeMessage),
+        Insert(OwnedInsertMessage),
+        Update(OwnedUpdateMessage),
+        Delete(OwnedDeleteMessage),
+        Truncate(TruncateMessage),
+        StreamStart(StreamStartMessage),
+        StreamStop(StreamStopMessage),
+        StreamCommit(StreamCommitMessage),
+        StreamAbort(StreamAbortMessage),
+        BeginPrepare(BeginPrepareMessage),
+        Prepare(PrepareMessage),
+        CommitPrepared(CommitPreparedMessage),
+        RollbackPrepared(RollbackPreparedMessage),
+        StreamPrepare(StreamPrepareMessage),
+        Unknown {
+            tag: u8,
+            #[serde(with = "crate::serde_base64")]
+            payload: Vec<u8>,
+        },
+    }
+}
+
+representations! {
+    #[serde(tag = "kind", content = "value")]
+    #[derive(Serialize)]
+    enum TaggedKind, CompactKind<'a> for "TupleDataKind" {
+        Null,
+        #[serde(rename = "unchanged")]
+        UnchangedToast,
+        Text(Cow<'a, str>),
+        Binary(#[serde(with = "crate::serde_base64")] &'a [u8]),
+    }
+}
+
+representations! {
+    #[serde(tag = "kind", content = "value")]
+    #[derive(Serialize, Deserialize)]
+    enum TaggedOwnedKind, CompactOwnedKind for "OwnedTupleDataKind" {
+        Null,
+        #[serde(rename = "unchanged")]
+        UnchangedToast,
+        Text(String),
+        Binary(#[serde(with = "crate::serde_base64")] Vec<u8>),
+    }
+}
+
+serialize!(ReplicationMessage<'_>, TaggedMessage, CompactMessage);
+serialize!(
+    OwnedReplicationMessage,
+    TaggedOwnedMessage,
+    CompactOwnedMessage
+);
+deserialize!(
+    OwnedReplicationMessage,
+    TaggedOwnedMessage,
+    CompactOwnedMessage
+);
+serialize!(TupleDataKind<'_>, TaggedKind, CompactKind);
+serialize!(OwnedTupleDataKind, TaggedOwnedKind, CompactOwnedKind);
+deserialize!(OwnedTupleDataKind, TaggedOwnedKind, CompactOwnedKind);
@@ -0,0 +1,113 @@
+use alloc::collections::BTreeMap;
+
+use crate::{ReplicationMessage, TupleData, TupleDataKind};
+
+/// Counts of the changes of a relation, accumulated by [`ChangeStats`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RelationStats {
+    /// Number of inserted rows.
+    pub inserts: u64,
+    /// Number of updated rows.
+    pub updates: u64,
+    /// Number of deleted rows.
+    pub deletes: u64,
+    /// Number of times the relation was truncated.
+    pub truncates: u64,
+    /// Total length in bytes of the column values of the tuples of the changes, old and key
+    /// tuples included. NULL and unchanged TOASTed values count as 0 bytes.
+    pub tuple_bytes: u64,
+}
+
+/// Accumulates counts of inserts, updates, deletes and truncates per relation, e.g. to export
+/// them as metrics.
+///
+/// Every message is observed, and those that aren't row changes are ignored. Changes of
+/// streamed transactions are counted as they're received, whether the transaction is committed
+/// or aborted later.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChangeStats {
+    relations: BTreeMap<i32, RelationStats>,
+}
+
+impl ChangeStats {
+    /// Creates an accumulator without any change counted.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Counts the changes of the message.
+    pub fn observe(&mut self, message: &ReplicationMessage<'_>) {
+        match message {
+            ReplicationMessage::Insert(insert) => {
+                let stats = self.relations.entry(insert.oid).or_default();
+                stats.inserts += 1;
+                stats.tuple_bytes += tuple_bytes(&insert.data);
+            }
+            ReplicationMessage::Update(update) => {
+                let stats = self.relations.entry(update.oid).or_default();
+                stats.updates += 1;
+                stats.tuple_bytes += [&update.key, &update.old]
+                    .into_iter()
+                    .flatten()
+                    .chain([&update.new])
+                    .map(tuple_bytes)
+                    .sum::<u64>();
+            }
+            ReplicationMessage::Delete(delete) => {
+                let stats = self.relations.entry(delete.oid).or_default();
+                stats.deletes += 1;
+                stats.tuple_bytes += [&delete.key, &delete.old]
+                    .into_iter()
+                    .flatten()
+                    .map(tuple_bytes)
+                    .sum::<u64>();
+            }
+            ReplicationMessage::Truncate(truncate) => {
+                for &oid in &truncate.oids {
+                    self.relations.entry(oid).or_default().truncates += 1;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The counts of the relation with the given OID, `None` if none of its changes was seen.
+    pub fn get(&self, oid: i32) -> Option<&RelationStats> {
+        self.relations.get(&oid)
+    }
+
+    /// The counts so far, keyed by relation OID.
+    pub fn snapshot(&self) -> BTreeMap<i32, RelationStats> {
+        self.relations.clone()
+    }
+
+    /// The counts of all relations added up.
+    pub fn total(&self) -> RelationStats {
+        self.relations
+            .values()
+            .fold(RelationStats::default(), |total, stats| RelationStats {
+                inserts: total.inserts + stats.inserts,
+                updates: total.updates + stats.updates,
+                deletes: total.deletes + stats.deletes,
+                truncates: total.truncates + stats.truncates,
+                tuple_bytes: total.tuple_bytes + stats.tuple_bytes,
+            })
+    }
+
+    /// Resets all counts, e.g. after exporting a snapshot.
+    pub fn clear(&mut self) {
+        self.relations.clear();
+    }
+}
+
+fn tuple_bytes(data: &TupleData<'_>) -> u64 {
+    data.columns
+        .iter()
+        .map(|column| match &column.kind {
+            TupleDataKind::Null | TupleDataKind::UnchangedToast => 0,
+            TupleDataKind::Text(text) => text.len() as u64,
+            TupleDataKind::Binary(bytes) => bytes.len() as u64,
+        })
+        .sum()
+}
@@ -0,0 +1,132 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+use crate::{OwnedReplicationMessage, ReplicationMessage, Xid};
+
+/// Reassembles transactions streamed with protocol version 2 or later.
+///
+/// A large in-progress transaction is sent in chunks enclosed in `StreamStart`/`StreamStop`,
+/// which can be interleaved with chunks of other transactions. The reassembler buffers the
+/// messages of every chunk by the xid of its `StreamStart`, and hands out the whole
+/// transaction once it's committed.
+///
+/// Messages outside of stream blocks are ignored, so every message passing through can be pushed.
+///
+/// Within a chunk, every change carries the xid of the subtransaction that made it, so a
+/// `StreamAbort` of a subtransaction (e.g. `ROLLBACK TO SAVEPOINT`) discards only its changes,
+/// and the changes of the subtransactions started after it, like Postgres' apply worker does.
+///
+/// NOTE: The messages must be parsed with [`ProtocolVersion::V2`](crate::ProtocolVersion::V2)
+/// or later, otherwise `StreamStart` doesn't carry an xid and its chunk is ignored.
+#[derive(Debug, Clone, Default)]
+pub struct StreamReassembler {
+    transactions: BTreeMap<Xid, StreamedTransaction>,
+    current: Option<Xid>,
+}
+
+#[derive(Debug, Clone, Default)]
+struct StreamedTransaction {
+    messages: Vec<OwnedReplicationMessage>,
+    /// Subtransactions in the order of their first change, with the position of that change.
+    subtransactions: Vec<(Xid, usize)>,
+}
+
+impl StreamedTransaction {
+    fn push(&mut self, xid: Xid, subxid: Option<Xid>, message: OwnedReplicationMessage) {
+        if let Some(subxid) = subxid.filter(|subxid| *subxid != xid) {
+            if !self
+                .subtransactions
+                .iter()
+                .any(|(known, _)| *known == subxid)
+            {
+                self.subtransactions.push((subxid, self.messages.len()));
+            }
+        }
+        self.messages.push(message);
+    }
+
+    fn abort_subtransaction(&mut self, subxid: Xid) {
+        if let Some(index) = self
+            .subtransactions
+            .iter()
+            .position(|(known, _)| *known == subxid)
+        {
+            self.messages.truncate(self.subtransactions[index].1);
+            self.subtransactions.truncate(index);
+        }
+    }
+}
+
+impl StreamReassembler {
+    /// Creates a reassembler without any buffered transactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts the next message of the stream.
+    ///
+    /// Returns the buffered messages of a streamed transaction when `message` is its
+    /// `StreamCommit` or `StreamPrepare`, which is included as the last message of the batch.
+    /// `StreamStart` and `StreamStop` are not buffered. `StreamAbort` of a top-level transaction
+    /// discards its messages, and of a subtransaction the messages since its first change.
+    pub fn push(
+        &mut self,
+        message: &ReplicationMessage<'_>,
+    ) -> Option<Vec<OwnedReplicationMessage>> {
+        match message {
+            ReplicationMessage::StreamStart(start) => {
+                self.current = start.transaction_id;
+                if let Some(xid) = start.transaction_id {
+                    self.transactions.entry(xid).or_default();
+                }
+                None
+            }
+            ReplicationMessage::StreamStop(_) => {
+                self.current = None;
+                None
+            }
+            ReplicationMessage::StreamCommit(commit) => self.finish(commit.transaction_id, message),
+            ReplicationMessage::StreamPrepare(prepare) => {
+                self.finish(prepare.transaction_id, message)
+            }
+            ReplicationMessage::StreamAbort(abort) => {
+                if abort.subtransaction_id == abort.transaction_id {
+                    self.transactions.remove(&abort.transaction_id);
+                } else if let Some(transaction) = self.transactions.get_mut(&abort.transaction_id) {
+                    transaction.abort_subtransaction(abort.subtransaction_id);
+                }
+                None
+            }
+            _ => {
+                let xid = self.current?;
+                self.transactions.entry(xid).or_default().push(
+                    xid,
+                    message.transaction_id(),
+                    message.to_owned(),
+                );
+                None
+            }
+        }
+    }
+
+    /// Returns the number of streamed transactions that are neither committed nor aborted yet.
+    pub fn in_progress(&self) -> usize {
+        self.transactions.len()
+    }
+
+    /// Discards all buffered transactions, e.g. after reconnecting to the replication slot.
+    pub fn clear(&mut self) {
+        self.transactions.clear();
+        self.current = None;
+    }
+
+    fn finish(
+        &mut self,
+        xid: Xid,
+        message: &ReplicationMessage<'_>,
+    ) -> Option<Vec<OwnedReplicationMessage>> {
+        let mut messages = self.transactions.remove(&xid)?.messages;
+        messages.push(message.to_owned());
+        Some(messages)
+    }
+}
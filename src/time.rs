@@ -0,0 +1,111 @@
+use time::format_description::BorrowedFormatItem;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::timestamp::{split_time_micros, unix_micros};
+use crate::value::{fixed, FromValue};
+use crate::{TupleDataColumn, ValueDecodeError};
+
+/// Julian day of the Postgres epoch (2000-01-01).
+const PG_EPOCH_JULIAN_DAY: i32 = 2_451_545;
+
+const DATE: &[BorrowedFormatItem<'_>] = format_description!("[year]-[month]-[day]");
+const TIME: &[BorrowedFormatItem<'_>] =
+    format_description!("[hour]:[minute]:[second][optional [.[subsecond]]]");
+const TIMESTAMP: &[BorrowedFormatItem<'_>] =
+    format_description!("[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]]");
+const TIMESTAMPTZ: &[BorrowedFormatItem<'_>] = format_description!(
+    "[year]-[month]-[day] [hour]:[minute]:[second][optional [.[subsecond]]][offset_hour sign:mandatory][optional [:[offset_minute]]]"
+);
+
+impl TupleDataColumn<'_> {
+    /// Decodes a `timestamptz` value, like [`TupleDataColumn::as_timestamptz`], into an
+    /// [`OffsetDateTime`] in UTC.
+    pub fn as_offset_date_time(&self) -> Result<OffsetDateTime, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `timestamp` (without time zone) value, like
+    /// [`TupleDataColumn::as_timestamp`], into a [`PrimitiveDateTime`].
+    pub fn as_primitive_date_time(&self) -> Result<PrimitiveDateTime, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `date` value, like [`TupleDataColumn::as_date`], into a [`time::Date`].
+    pub fn as_time_date(&self) -> Result<Date, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `time` value, like [`TupleDataColumn::as_time`], into a [`time::Time`].
+    pub fn as_time_time(&self) -> Result<Time, ValueDecodeError> {
+        self.value()
+    }
+}
+
+/// Converts microseconds since the Postgres epoch to an [`OffsetDateTime`] in UTC.
+fn from_pg_micros(micros: i64) -> Option<OffsetDateTime> {
+    let nanos = i128::from(unix_micros(micros)?) * 1_000;
+
+    OffsetDateTime::from_unix_timestamp_nanos(nanos).ok()
+}
+
+impl FromValue for OffsetDateTime {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        OffsetDateTime::parse(text, TIMESTAMPTZ)
+            .map(|timestamp| timestamp.to_offset(UtcOffset::UTC))
+            .map_err(ValueDecodeError::ParseTime)
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        from_pg_micros(i64::from_be_bytes(fixed(bytes)?)).ok_or(ValueDecodeError::InvalidValue {
+            type_name: "timestamptz",
+        })
+    }
+}
+
+impl FromValue for PrimitiveDateTime {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        PrimitiveDateTime::parse(text, TIMESTAMP).map_err(ValueDecodeError::ParseTime)
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        from_pg_micros(i64::from_be_bytes(fixed(bytes)?))
+            .map(|timestamp| PrimitiveDateTime::new(timestamp.date(), timestamp.time()))
+            .ok_or(ValueDecodeError::InvalidValue {
+                type_name: "timestamp",
+            })
+    }
+}
+
+impl FromValue for Date {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        Date::parse(text, DATE).map_err(ValueDecodeError::ParseTime)
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        PG_EPOCH_JULIAN_DAY
+            .checked_add(i32::from_be_bytes(fixed(bytes)?))
+            .and_then(|day| Date::from_julian_day(day).ok())
+            .ok_or(ValueDecodeError::InvalidValue { type_name: "date" })
+    }
+}
+
+impl FromValue for Time {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        Time::parse(text, TIME).map_err(ValueDecodeError::ParseTime)
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        split_time_micros(i64::from_be_bytes(fixed(bytes)?))
+            .and_then(|(seconds, micros)| {
+                Time::from_hms_micro(
+                    (seconds / 3_600) as u8,
+                    (seconds / 60 % 60) as u8,
+                    (seconds % 60) as u8,
+                    micros,
+                )
+                .ok()
+            })
+            .ok_or(ValueDecodeError::InvalidValue { type_name: "time" })
+    }
+}
@@ -0,0 +1,53 @@
+use chrono::{DateTime, Utc};
+
+/// Microseconds between the Unix epoch and the Postgres epoch (2000-01-01 00:00:00 UTC).
+pub(crate) const PG_EPOCH_OFFSET_MICROS: i64 = 946_684_800_000_000;
+
+/// Converts microseconds since the Postgres epoch (2000-01-01 00:00:00 UTC) to a [`DateTime`].
+///
+/// Negative values are timestamps before 2000. Values outside of the range supported by
+/// [`DateTime`], including Postgres' `infinity` and `-infinity` (`i64::MAX` and `i64::MIN`),
+/// saturate to [`DateTime::<Utc>::MAX_UTC`] and [`DateTime::<Utc>::MIN_UTC`].
+pub fn pg_timestamp_from_micros(micros: i64) -> DateTime<Utc> {
+    from_pg_micros(micros).unwrap_or(if micros < 0 {
+        DateTime::<Utc>::MIN_UTC
+    } else {
+        DateTime::<Utc>::MAX_UTC
+    })
+}
+
+/// Converts a [`DateTime`] to microseconds since the Postgres epoch (2000-01-01 00:00:00 UTC).
+///
+/// Sub-microsecond precision, which Postgres doesn't have, is truncated, and timestamps too
+/// early for an `i64` (far outside of the Postgres range) saturate.
+pub fn pg_timestamp_to_micros(timestamp: DateTime<Utc>) -> i64 {
+    timestamp
+        .timestamp_micros()
+        .saturating_sub(PG_EPOCH_OFFSET_MICROS)
+}
+
+/// Converts microseconds since the Postgres epoch to a [`DateTime`].
+///
+/// Returns `None` if the timestamp is outside of the range supported by [`DateTime`].
+pub(crate) fn from_pg_micros(micros: i64) -> Option<DateTime<Utc>> {
+    unix_micros(micros).and_then(DateTime::from_timestamp_micros)
+}
+
+/// Converts microseconds since the Postgres epoch to microseconds since the Unix epoch.
+pub(crate) fn unix_micros(micros: i64) -> Option<i64> {
+    micros.checked_add(PG_EPOCH_OFFSET_MICROS)
+}
+
+/// Splits microseconds since midnight, as sent for binary `time` values, into seconds since
+/// midnight and microseconds within the second.
+///
+/// Returns `None` for negative values and values of a day or more.
+pub(crate) fn split_time_micros(micros: i64) -> Option<(u32, u32)> {
+    const MICROS_PER_DAY: i64 = 86_400_000_000;
+
+    if !(0..MICROS_PER_DAY).contains(&micros) {
+        return None;
+    }
+
+    Some(((micros / 1_000_000) as u32, (micros % 1_000_000) as u32))
+}
@@ -0,0 +1,106 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::sync::Arc;
+use core::fmt;
+
+use crate::{
+    TupleDataColumn, ValueDecodeError, BOOL_OID, BPCHAR_OID, FLOAT4_OID, FLOAT8_OID, INT2_OID,
+    INT4_OID, INT8_OID, NAME_OID, OID_OID, TEXT_OID, VARCHAR_OID,
+};
+
+type Decoder<V> = Arc<dyn Fn(&TupleDataColumn<'_>) -> Result<V, ValueDecodeError> + Send + Sync>;
+
+/// Decoders of column values into `V` by type OID, which can be extended with decoders of
+/// extension or user-defined types (e.g. PostGIS `geometry`), whose OIDs depend on the database.
+///
+/// The type OIDs of columns are found in the [`RelationMessage`](crate::RelationMessage).
+pub struct TypeRegistry<V> {
+    decoders: BTreeMap<i32, Decoder<V>>,
+}
+
+impl<V> TypeRegistry<V> {
+    /// Creates a registry without any decoder.
+    pub fn new() -> Self {
+        Self {
+            decoders: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the decoder of values of the type with the given OID, replacing the previous
+    /// one.
+    pub fn register(
+        &mut self,
+        oid: i32,
+        decoder: impl Fn(&TupleDataColumn<'_>) -> Result<V, ValueDecodeError> + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.decoders.insert(oid, Arc::new(decoder));
+        self
+    }
+
+    /// Whether a decoder is registered for the type with the given OID.
+    pub fn contains(&self, oid: i32) -> bool {
+        self.decoders.contains_key(&oid)
+    }
+
+    /// Decodes the value of a column of the type with the given OID.
+    ///
+    /// Returns [`ValueDecodeError::UnknownType`] if no decoder is registered for the type.
+    pub fn decode(&self, oid: i32, column: &TupleDataColumn<'_>) -> Result<V, ValueDecodeError> {
+        let decoder = self
+            .decoders
+            .get(&oid)
+            .ok_or(ValueDecodeError::UnknownType(oid))?;
+
+        decoder(column)
+    }
+}
+
+impl<V> TypeRegistry<V>
+where
+    V: From<bool> + From<i16> + From<i32> + From<i64> + From<f32> + From<f64> + From<String>,
+{
+    /// Creates a registry with decoders of the built-in boolean, integer, floating point and
+    /// character types, e.g. for `serde_json::Value`.
+    ///
+    /// `oid` values are decoded as `i64`, since they're unsigned.
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry
+            .register(BOOL_OID, |column| column.as_bool().map(V::from))
+            .register(INT8_OID, |column| column.as_i64().map(V::from))
+            .register(INT2_OID, |column| column.as_i16().map(V::from))
+            .register(INT4_OID, |column| column.as_i32().map(V::from))
+            .register(OID_OID, |column| {
+                column.as_oid().map(|oid| V::from(i64::from(oid)))
+            })
+            .register(FLOAT4_OID, |column| column.as_f32().map(V::from))
+            .register(FLOAT8_OID, |column| column.as_f64().map(V::from));
+        for oid in [NAME_OID, TEXT_OID, BPCHAR_OID, VARCHAR_OID] {
+            registry.register(oid, |column| column.value::<String>().map(V::from));
+        }
+
+        registry
+    }
+}
+
+impl<V> Default for TypeRegistry<V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> Clone for TypeRegistry<V> {
+    fn clone(&self) -> Self {
+        Self {
+            decoders: self.decoders.clone(),
+        }
+    }
+}
+
+impl<V> fmt::Debug for TypeRegistry<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TypeRegistry")
+            .field("oids", &self.decoders.keys())
+            .finish()
+    }
+}
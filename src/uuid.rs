@@ -0,0 +1,21 @@
+use uuid::Uuid;
+
+use crate::value::{fixed, FromValue};
+use crate::{TupleDataColumn, ValueDecodeError};
+
+impl TupleDataColumn<'_> {
+    /// Decodes a `uuid` value.
+    pub fn as_uuid(&self) -> Result<Uuid, ValueDecodeError> {
+        self.value()
+    }
+}
+
+impl FromValue for Uuid {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        Uuid::try_parse(text).map_err(ValueDecodeError::ParseUuid)
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        Ok(Uuid::from_bytes(fixed(bytes)?))
+    }
+}
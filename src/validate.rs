@@ -0,0 +1,129 @@
+use crate::error::ValidationError;
+use crate::{Lsn, ReplicationMessage, TupleData};
+
+/// Maximum number of columns of a table (`MaxHeapAttributeNumber`).
+const MAX_RELATION_COLUMNS: usize = 1600;
+
+/// Maximum number of columns of a row (`MaxTupleAttributeNumber`).
+const MAX_TUPLE_COLUMNS: usize = 1664;
+
+impl ReplicationMessage<'_> {
+    /// Checks invariants between the fields of the message that the parser doesn't enforce,
+    /// to defend against a corrupt upstream or a message built by hand.
+    ///
+    /// The checks only involve the message itself: e.g. a non-transactional generic message
+    /// must not have an xid, the end LSN of a transaction must not be before its commit LSN,
+    /// and tuples must not have more columns than Postgres allows. Whether a tuple matches its
+    /// relation is checked with
+    /// [`RelationMessage::validate_tuple`](crate::RelationMessage::validate_tuple).
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        match self {
+            Self::Generic(message) => {
+                if !message.is_transactional && message.transaction_id.is_some() {
+                    return Err(ValidationError::NonTransactionalXid);
+                }
+                if usize::try_from(message.length).ok() != Some(message.content.len()) {
+                    return Err(ValidationError::GenericLengthMismatch {
+                        length: message.length,
+                        actual: message.content.len(),
+                    });
+                }
+            }
+            Self::Commit(message) => lsns(message.lsn, message.final_lsn)?,
+            Self::Relation(message) => {
+                if message.columns.len() > MAX_RELATION_COLUMNS {
+                    return Err(ValidationError::TooManyRelationColumns {
+                        count: message.columns.len(),
+                    });
+                }
+            }
+            Self::Insert(message) => tuple(&message.data)?,
+            Self::Update(message) => {
+                key_or_old(message.key.as_ref(), message.old.as_ref())?;
+                tuple(&message.new)?;
+                if let Some(old) = &message.old {
+                    tuple(old)?;
+                    if old.columns.len() != message.new.columns.len() {
+                        return Err(ValidationError::TupleLengthMismatch {
+                            old: old.columns.len(),
+                            new: message.new.columns.len(),
+                        });
+                    }
+                }
+                if let Some(key) = &message.key {
+                    tuple(key)?;
+                }
+            }
+            Self::Delete(message) => {
+                key_or_old(message.key.as_ref(), message.old.as_ref())?;
+                for data in message.key.iter().chain(&message.old) {
+                    tuple(data)?;
+                }
+            }
+            Self::StreamCommit(message) => lsns(message.lsn, message.final_lsn)?,
+            Self::StreamAbort(message) => {
+                if message.abort_lsn.is_some() != message.abort_timestamp.is_some() {
+                    return Err(ValidationError::IncompleteAbortInfo);
+                }
+            }
+            Self::BeginPrepare(message) => {
+                lsns(message.lsn, message.final_lsn)?;
+                message.gid().map_err(ValidationError::GidTooLong)?;
+            }
+            Self::Prepare(message) => {
+                lsns(message.lsn, message.final_lsn)?;
+                message.gid().map_err(ValidationError::GidTooLong)?;
+            }
+            Self::CommitPrepared(message) => {
+                lsns(message.lsn, message.final_lsn)?;
+                message.gid().map_err(ValidationError::GidTooLong)?;
+            }
+            Self::RollbackPrepared(message) => {
+                lsns(message.lsn, message.final_lsn)?;
+                message.gid().map_err(ValidationError::GidTooLong)?;
+            }
+            Self::StreamPrepare(message) => {
+                lsns(message.lsn, message.final_lsn)?;
+                message.gid().map_err(ValidationError::GidTooLong)?;
+            }
+            Self::Begin(_)
+            | Self::Origin(_)
+            | Self::Type(_)
+            | Self::Truncate(_)
+            | Self::StreamStart(_)
+            | Self::StreamStop(_)
+            | Self::Unknown { .. } => {}
+        }
+
+        Ok(())
+    }
+}
+
+fn lsns(lsn: Lsn, final_lsn: Lsn) -> Result<(), ValidationError> {
+    if final_lsn < lsn {
+        return Err(ValidationError::FinalLsnBeforeLsn { lsn, final_lsn });
+    }
+
+    Ok(())
+}
+
+fn tuple(data: &TupleData<'_>) -> Result<(), ValidationError> {
+    if data.columns.len() > MAX_TUPLE_COLUMNS {
+        return Err(ValidationError::TooManyColumns {
+            count: data.columns.len(),
+        });
+    }
+
+    Ok(())
+}
+
+fn key_or_old(
+    key: Option<&TupleData<'_>>,
+    old: Option<&TupleData<'_>>,
+) -> Result<(), ValidationError> {
+    if key.is_some() && old.is_some() {
+        return Err(ValidationError::KeyAndOldTuple);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,269 @@
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+
+use chrono::{DateTime, Days, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+
+use crate::{timestamp, TupleDataColumn, TupleDataKind, ValueDecodeError};
+
+/// A type that can be decoded from a [`TupleDataColumn`] value.
+///
+/// Implementations handle both the text and the binary output format of the Postgres type.
+pub trait FromValue: Sized {
+    /// Decodes the value from its text format.
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError>;
+
+    /// Decodes the value from its binary format.
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError>;
+}
+
+impl TupleDataColumn<'_> {
+    /// Decodes the value of the column as `T`.
+    ///
+    /// Returns [`ValueDecodeError::Null`] for NULL values and
+    /// [`ValueDecodeError::UnchangedToast`] for unchanged TOASTed values.
+    pub fn value<T: FromValue>(&self) -> Result<T, ValueDecodeError> {
+        match &self.kind {
+            TupleDataKind::Null => Err(ValueDecodeError::Null),
+            TupleDataKind::UnchangedToast => Err(ValueDecodeError::UnchangedToast),
+            TupleDataKind::Text(text) => T::from_text(text),
+            TupleDataKind::Binary(bytes) => T::from_binary(bytes),
+        }
+    }
+
+    /// Returns the label of a value of a user-defined enum type.
+    ///
+    /// Enum types get their OIDs when they're created, so their columns can't be told apart by
+    /// type OID; the [`RelationMessage`](crate::RelationMessage) only tells the OID, which can be
+    /// looked up in `pg_type`. Both formats carry the label itself: the binary format of an enum
+    /// value is its label as UTF-8.
+    pub fn as_enum_label(&self) -> Result<&str, ValueDecodeError> {
+        match &self.kind {
+            TupleDataKind::Null => Err(ValueDecodeError::Null),
+            TupleDataKind::UnchangedToast => Err(ValueDecodeError::UnchangedToast),
+            TupleDataKind::Text(text) => Ok(text),
+            TupleDataKind::Binary(bytes) => {
+                core::str::from_utf8(bytes).map_err(ValueDecodeError::Utf8)
+            }
+        }
+    }
+
+    /// Decodes an `int2` value.
+    pub fn as_i16(&self) -> Result<i16, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes an `int4` value.
+    pub fn as_i32(&self) -> Result<i32, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes an `int8` value.
+    pub fn as_i64(&self) -> Result<i64, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes an `oid` value, or a value of one of the `reg*` types (e.g. `regclass`).
+    ///
+    /// OIDs are unsigned, so values above `i32::MAX` are preserved. The `reg*` types are sent
+    /// as object names in text format, so only their binary format can be decoded.
+    pub fn as_oid(&self) -> Result<u32, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `bool` value (`t`/`f` in text format).
+    pub fn as_bool(&self) -> Result<bool, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a single-byte `"char"` value (OID 18), not to be confused with `char(n)`.
+    ///
+    /// Only ASCII characters are accepted. Postgres sends the NUL character as an empty text
+    /// value.
+    pub fn as_char(&self) -> Result<char, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `timestamptz` value.
+    ///
+    /// Text values are expected in the `ISO` date style (e.g. `2024-01-02 03:04:05.123456+00`).
+    pub fn as_timestamptz(&self) -> Result<DateTime<Utc>, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `timestamp` (without time zone) value, as the wall-clock time that was stored.
+    ///
+    /// Text values are expected in the `ISO` date style (e.g. `2024-01-02 03:04:05.123456`).
+    /// Unlike [`TupleDataColumn::as_timestamptz`], no offset is applied.
+    pub fn as_timestamp(&self) -> Result<NaiveDateTime, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `date` value.
+    ///
+    /// Text values are expected in the `ISO` date style (e.g. `2024-06-15`).
+    pub fn as_date(&self) -> Result<NaiveDate, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `time` value (e.g. `13:45:30.5` in text format).
+    pub fn as_time(&self) -> Result<NaiveTime, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `float4` value.
+    pub fn as_f32(&self) -> Result<f32, ValueDecodeError> {
+        self.value()
+    }
+
+    /// Decodes a `float8` value.
+    pub fn as_f64(&self) -> Result<f64, ValueDecodeError> {
+        self.value()
+    }
+}
+
+/// Checks that a binary value is exactly `N` bytes long.
+pub(crate) fn fixed<const N: usize>(bytes: &[u8]) -> Result<[u8; N], ValueDecodeError> {
+    bytes
+        .try_into()
+        .map_err(|_| ValueDecodeError::UnexpectedLength {
+            expected: N,
+            actual: bytes.len(),
+        })
+}
+
+macro_rules! impl_from_value_for_number {
+    ($($ty:ty => $error:ident),* $(,)?) => {
+        $(
+            impl FromValue for $ty {
+                fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+                    text.parse().map_err(ValueDecodeError::$error)
+                }
+
+                fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+                    Ok(Self::from_be_bytes(fixed(bytes)?))
+                }
+            }
+        )*
+    };
+}
+
+impl_from_value_for_number! {
+    i16 => ParseInt,
+    i32 => ParseInt,
+    i64 => ParseInt,
+    u32 => ParseInt,
+    f32 => ParseFloat,
+    f64 => ParseFloat,
+}
+
+impl FromValue for bool {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        match text {
+            "t" => Ok(true),
+            "f" => Ok(false),
+            _ => Err(ValueDecodeError::InvalidValue { type_name: "bool" }),
+        }
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        match fixed(bytes)? {
+            [1] => Ok(true),
+            [0] => Ok(false),
+            _ => Err(ValueDecodeError::InvalidValue { type_name: "bool" }),
+        }
+    }
+}
+
+impl FromValue for char {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        match text.as_bytes() {
+            [] => Ok('\0'),
+            [byte] if byte.is_ascii() => Ok(char::from(*byte)),
+            _ => Err(ValueDecodeError::InvalidValue { type_name: "char" }),
+        }
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        match fixed(bytes)? {
+            [byte] if byte.is_ascii() => Ok(char::from(byte)),
+            _ => Err(ValueDecodeError::InvalidValue { type_name: "char" }),
+        }
+    }
+}
+
+impl FromValue for DateTime<Utc> {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        DateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f%#z")
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+            .map_err(ValueDecodeError::ParseDateTime)
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        timestamp::from_pg_micros(i64::from_be_bytes(fixed(bytes)?)).ok_or(
+            ValueDecodeError::InvalidValue {
+                type_name: "timestamptz",
+            },
+        )
+    }
+}
+
+impl FromValue for NaiveDateTime {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        NaiveDateTime::parse_from_str(text, "%Y-%m-%d %H:%M:%S%.f")
+            .map_err(ValueDecodeError::ParseDateTime)
+    }
+
+    /// Binary `timestamp` values are microseconds since the Postgres epoch, like `timestamptz`
+    /// values, but counted in local time.
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        timestamp::from_pg_micros(i64::from_be_bytes(fixed(bytes)?))
+            .map(|timestamp| timestamp.naive_utc())
+            .ok_or(ValueDecodeError::InvalidValue {
+                type_name: "timestamp",
+            })
+    }
+}
+
+impl FromValue for String {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        Ok(text.to_owned())
+    }
+
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        String::from_utf8(bytes.to_vec()).map_err(|err| ValueDecodeError::Utf8(err.utf8_error()))
+    }
+}
+
+impl FromValue for NaiveDate {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").map_err(ValueDecodeError::ParseDateTime)
+    }
+
+    /// Binary `date` values are days since the Postgres epoch (2000-01-01).
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        let days = i32::from_be_bytes(fixed(bytes)?);
+        let epoch = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+        let date = if days < 0 {
+            epoch.checked_sub_days(Days::new(days.unsigned_abs().into()))
+        } else {
+            epoch.checked_add_days(Days::new(days as u64))
+        };
+
+        date.ok_or(ValueDecodeError::InvalidValue { type_name: "date" })
+    }
+}
+
+impl FromValue for NaiveTime {
+    fn from_text(text: &str) -> Result<Self, ValueDecodeError> {
+        NaiveTime::parse_from_str(text, "%H:%M:%S%.f").map_err(ValueDecodeError::ParseDateTime)
+    }
+
+    /// Binary `time` values are microseconds since midnight.
+    fn from_binary(bytes: &[u8]) -> Result<Self, ValueDecodeError> {
+        timestamp::split_time_micros(i64::from_be_bytes(fixed(bytes)?))
+            .and_then(|(seconds, micros)| {
+                NaiveTime::from_num_seconds_from_midnight_opt(seconds, micros * 1_000)
+            })
+            .ok_or(ValueDecodeError::InvalidValue { type_name: "time" })
+    }
+}
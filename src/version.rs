@@ -0,0 +1,20 @@
+/// Version of the pgoutput logical replication protocol (`proto_version` option).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ProtocolVersion {
+    /// The base protocol, supported since Postgres 10.
+    #[default]
+    V1,
+    /// Adds streaming of large in-progress transactions (Postgres 14).
+    V2,
+    /// Adds two-phase commit messages (Postgres 15).
+    V3,
+    /// Adds parallel apply of streamed transactions (Postgres 16).
+    V4,
+}
+
+impl ProtocolVersion {
+    /// Does this version carry the xid of streamed transactions in data messages?
+    pub fn has_streamed_xid(self) -> bool {
+        self >= Self::V2
+    }
+}
@@ -0,0 +1,58 @@
+use crate::*;
+
+/// Handlers for every type of [`ReplicationMessage`], called by [`ReplicationMessage::accept`].
+///
+/// All methods do nothing by default, so only the handlers for the messages of interest need
+/// to be implemented.
+#[allow(unused_variables)]
+pub trait MessageVisitor<'a> {
+    fn visit_begin(&mut self, message: BeginMessage) {}
+    fn visit_generic(&mut self, message: GenericMessage<'a>) {}
+    fn visit_commit(&mut self, message: CommitMessage) {}
+    fn visit_origin(&mut self, message: OriginMessage) {}
+    fn visit_relation(&mut self, message: RelationMessage) {}
+    fn visit_type(&mut self, message: TypeMessage) {}
+    fn visit_insert(&mut self, message: InsertMessage<'a>) {}
+    fn visit_update(&mut self, message: UpdateMessage<'a>) {}
+    fn visit_delete(&mut self, message: DeleteMessage<'a>) {}
+    fn visit_truncate(&mut self, message: TruncateMessage) {}
+    fn visit_stream_start(&mut self, message: StreamStartMessage) {}
+    fn visit_stream_stop(&mut self, message: StreamStopMessage) {}
+    fn visit_stream_commit(&mut self, message: StreamCommitMessage) {}
+    fn visit_stream_abort(&mut self, message: StreamAbortMessage) {}
+    fn visit_begin_prepare(&mut self, message: BeginPrepareMessage) {}
+    fn visit_prepare(&mut self, message: PrepareMessage) {}
+    fn visit_commit_prepared(&mut self, message: CommitPreparedMessage) {}
+    fn visit_rollback_prepared(&mut self, message: RollbackPreparedMessage) {}
+    fn visit_stream_prepare(&mut self, message: StreamPrepareMessage) {}
+    /// Called for [`ReplicationMessage::Unknown`].
+    fn visit_unknown(&mut self, tag: u8, payload: &'a [u8]) {}
+}
+
+impl<'a> ReplicationMessage<'a> {
+    /// Passes the message to the handler of its type.
+    pub fn accept(self, visitor: &mut impl MessageVisitor<'a>) {
+        match self {
+            Self::Begin(message) => visitor.visit_begin(message),
+            Self::Generic(message) => visitor.visit_generic(message),
+            Self::Commit(message) => visitor.visit_commit(message),
+            Self::Origin(message) => visitor.visit_origin(message),
+            Self::Relation(message) => visitor.visit_relation(message),
+            Self::Type(message) => visitor.visit_type(message),
+            Self::Insert(message) => visitor.visit_insert(message),
+            Self::Update(message) => visitor.visit_update(message),
+            Self::Delete(message) => visitor.visit_delete(message),
+            Self::Truncate(message) => visitor.visit_truncate(message),
+            Self::StreamStart(message) => visitor.visit_stream_start(message),
+            Self::StreamStop(message) => visitor.visit_stream_stop(message),
+            Self::StreamCommit(message) => visitor.visit_stream_commit(message),
+            Self::StreamAbort(message) => visitor.visit_stream_abort(message),
+            Self::BeginPrepare(message) => visitor.visit_begin_prepare(message),
+            Self::Prepare(message) => visitor.visit_prepare(message),
+            Self::CommitPrepared(message) => visitor.visit_commit_prepared(message),
+            Self::RollbackPrepared(message) => visitor.visit_rollback_prepared(message),
+            Self::StreamPrepare(message) => visitor.visit_stream_prepare(message),
+            Self::Unknown { tag, payload } => visitor.visit_unknown(tag, payload),
+        }
+    }
+}
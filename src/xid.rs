@@ -0,0 +1,55 @@
+use core::fmt;
+
+/// A transaction id.
+///
+/// Xids are 32-bit unsigned counters that wrap around, so the derived ordering is only the
+/// numeric one. Use [`Xid::precedes`] and [`Xid::follows`] to compare them the way Postgres does.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct Xid(pub u32);
+
+impl Xid {
+    /// The first xid assigned to a regular transaction; smaller ones are reserved.
+    pub const FIRST_NORMAL: Xid = Xid(3);
+
+    /// Is it a regular xid (not one of the reserved ones)?
+    pub fn is_normal(self) -> bool {
+        self >= Self::FIRST_NORMAL
+    }
+
+    /// Is this xid logically older than `other`, taking wraparound into account?
+    ///
+    /// Same as `TransactionIdPrecedes` in Postgres: reserved xids are compared numerically,
+    /// normal ones modulo 2^32.
+    pub fn precedes(self, other: Xid) -> bool {
+        if !self.is_normal() || !other.is_normal() {
+            return self.0 < other.0;
+        }
+
+        (self.0.wrapping_sub(other.0) as i32) < 0
+    }
+
+    /// Is this xid logically newer than `other`, taking wraparound into account?
+    pub fn follows(self, other: Xid) -> bool {
+        other.precedes(self)
+    }
+}
+
+impl fmt::Display for Xid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl From<u32> for Xid {
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+impl From<Xid> for u32 {
+    fn from(xid: Xid) -> Self {
+        xid.0
+    }
+}
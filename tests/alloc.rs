@@ -0,0 +1,214 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::borrow::Cow;
+use std::cell::Cell;
+
+use chrono::{DateTime, Utc};
+use postgres_replication_types::*;
+
+/// Counts the allocations of the current thread, so that tests running in parallel don't
+/// interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Returns the result of `f` and the number of allocations it made.
+fn allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = ALLOCATIONS.with(Cell::get);
+
+    (result, after - before)
+}
+
+fn timestamp() -> DateTime<Utc> {
+    pg_timestamp_from_micros(757_382_400_123_456)
+}
+
+fn tuple() -> TupleData<'static> {
+    TupleData::builder()
+        .text("42")
+        .null()
+        .unchanged_toast()
+        .text("a longer text value")
+        .binary(&[0, 0, 0, 42])
+        .build()
+}
+
+fn messages() -> Vec<ReplicationMessage<'static>> {
+    vec![
+        ReplicationMessage::Begin(BeginMessage {
+            final_lsn: Lsn(0x16_B374_D848),
+            timestamp: timestamp(),
+            transaction_id: Xid(734),
+        }),
+        ReplicationMessage::Generic(GenericMessage {
+            transaction_id: None,
+            is_transactional: false,
+            lsn: Lsn(1),
+            prefix: "prefix".into(),
+            length: 7,
+            content: b"content",
+        }),
+        ReplicationMessage::Origin(OriginMessage {
+            lsn: Lsn(2),
+            name: "origin".into(),
+        }),
+        ReplicationMessage::Relation(
+            RelationMessage::builder()
+                .oid(16385)
+                .namespace(Some("public"))
+                .name("users")
+                .key_column("id", 23)
+                .column("email", 25)
+                .build(),
+        ),
+        ReplicationMessage::Type(TypeMessage {
+            transaction_id: None,
+            oid: 16390,
+            namespace: Some("public".into()),
+            name: "mood".into(),
+        }),
+        ReplicationMessage::Insert(InsertMessage::builder().oid(16385).data(tuple()).build()),
+        ReplicationMessage::Update(
+            UpdateMessage::builder()
+                .oid(16385)
+                .old(tuple())
+                .new_tuple(tuple())
+                .build(),
+        ),
+        ReplicationMessage::Delete(DeleteMessage::builder().oid(16385).key(tuple()).build()),
+        ReplicationMessage::Truncate(TruncateMessage {
+            transaction_id: None,
+            is_cascade: true,
+            is_restart_identity: false,
+            oids: vec![16385, 16386, 16387],
+        }),
+        ReplicationMessage::Commit(CommitMessage {
+            lsn: Lsn(0x16_B374_D848),
+            final_lsn: Lsn(0x16_B374_D878),
+            timestamp: timestamp(),
+        }),
+        ReplicationMessage::StreamStop(StreamStopMessage {}),
+        ReplicationMessage::Prepare(PrepareMessage {
+            lsn: Lsn(3),
+            final_lsn: Lsn(4),
+            timestamp: timestamp(),
+            transaction_id: Xid(735),
+            gid: "gid".into(),
+        }),
+    ]
+}
+
+#[test]
+fn skip_consumes_as_much_as_parse_prefix_without_allocating() {
+    let mut buf = Vec::new();
+    for message in messages() {
+        message.encode(&mut buf).unwrap();
+    }
+
+    let mut offset = 0;
+    while offset < buf.len() {
+        let rest = &buf[offset..];
+        let (skipped, count) = allocations(|| ReplicationMessage::skip(rest));
+        let (message, consumed) = ReplicationMessage::parse_prefix(rest).unwrap();
+
+        assert_eq!(skipped, Ok(consumed), "{message}");
+        assert_eq!(count, 0, "{message}");
+        offset += consumed;
+    }
+}
+
+fn text_insert(columns: usize) -> Vec<u8> {
+    let mut data = TupleData::builder();
+    for column in 0..columns {
+        data = data.text(format!("text value of column {column}"));
+    }
+
+    let mut buf = Vec::new();
+    ReplicationMessage::Insert(
+        InsertMessage::builder()
+            .oid(16385)
+            .data(data.build())
+            .build(),
+    )
+    .encode(&mut buf)
+    .unwrap();
+    buf
+}
+
+#[test]
+fn text_values_are_borrowed_without_allocating() {
+    let narrow = text_insert(1);
+    let wide = text_insert(16);
+
+    for options in [
+        ParseOptions::default(),
+        ParseOptions::default().lossy_utf8(true),
+    ] {
+        // The only allocation is the vector of columns, whatever their number.
+        let (_, count) =
+            allocations(|| ReplicationMessage::parse_with_options(&narrow, options).unwrap());
+        assert_eq!(count, 1);
+
+        let (message, count) =
+            allocations(|| ReplicationMessage::parse_with_options(&wide, options).unwrap());
+        assert_eq!(count, 1);
+
+        let ReplicationMessage::Insert(insert) = message else {
+            panic!("expected an insert");
+        };
+        assert_eq!(insert.data.columns.len(), 16);
+        for column in &insert.data.columns {
+            assert!(matches!(column.kind, TupleDataKind::Text(Cow::Borrowed(_))));
+        }
+    }
+}
+
+#[test]
+fn into_owned_moves_owned_text_instead_of_cloning_it() {
+    let owned_text = || {
+        let mut data = TupleData::builder();
+        for column in 0..16 {
+            data = data.text(format!("text value of column {column}"));
+        }
+        data.build()
+    };
+
+    // Cloning copies every text value, besides allocating the vector of columns.
+    let tuple = owned_text();
+    let (_, count) = allocations(|| tuple.to_owned());
+    assert_eq!(count, 17);
+
+    let tuple = owned_text();
+    let pointers: Vec<_> = tuple
+        .columns
+        .iter()
+        .map(|column| column.as_text().unwrap().as_ptr())
+        .collect();
+    let (owned, count) = allocations(|| tuple.into_owned());
+    assert!(count <= 1);
+    for (column, pointer) in owned.columns.iter().zip(pointers) {
+        assert_eq!(column.as_text().unwrap().as_ptr(), pointer);
+    }
+}
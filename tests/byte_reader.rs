@@ -0,0 +1,100 @@
+use postgres_replication_types::*;
+
+#[test]
+fn integers_are_read_in_network_order() {
+    let buf = [
+        1, 0xff, 0, 2, 0, 0, 0, 3, 0xff, 0xff, 0xff, 0xfd, 0, 0, 0, 0, 0, 0, 0, 4, 0xff, 0xff,
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xfb,
+    ];
+    let mut reader = ByteReader::new(&buf);
+
+    assert_eq!(reader.read_u8().unwrap(), 1);
+    assert_eq!(reader.read_i8().unwrap(), -1);
+    assert_eq!(reader.read_i16().unwrap(), 2);
+    assert_eq!(reader.read_i32().unwrap(), 3);
+    assert_eq!(reader.read_u32().unwrap(), 0xffff_fffd);
+    assert_eq!(reader.read_i64().unwrap(), 4);
+    assert_eq!(reader.read_u64().unwrap(), u64::MAX - 4);
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn lsn_and_xid_are_read() {
+    let mut reader = ByteReader::new(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 2]);
+
+    assert_eq!(reader.read_xid().unwrap(), Xid(1));
+    assert_eq!(reader.read_lsn().unwrap(), Lsn(2));
+    assert!(reader.read_lsn().is_err());
+}
+
+#[test]
+fn bytes_and_strings_are_borrowed() {
+    let buf = [b'h', b'i', 0, 9, 8, 7];
+    let mut reader = ByteReader::new(&buf);
+
+    assert_eq!(reader.read_cstr().unwrap(), "hi");
+    assert_eq!(reader.position(), 3);
+    assert_eq!(reader.read_bytes(2).unwrap(), [9, 8]);
+    assert_eq!(reader.remaining(), 1);
+    assert_eq!(reader.read_rest(), [7]);
+    assert!(reader.is_empty());
+}
+
+#[test]
+fn eof_is_reported_without_advancing() {
+    let mut reader = ByteReader::new(&[0, 0, 0, 1, 2, 3, 4]);
+    reader.read_i32().unwrap();
+
+    assert_eq!(
+        reader.read_i32().unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 4,
+            needed: 4,
+            remaining: 3
+        }
+    );
+    assert_eq!(reader.position(), 4);
+    assert_eq!(
+        reader.read_cstr().unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 4,
+            needed: 4,
+            remaining: 3
+        }
+    );
+    assert!(reader.read_bytes(4).is_err());
+    assert_eq!(reader.read_rest(), [2, 3, 4]);
+    assert!(matches!(
+        reader.read_u8(),
+        Err(ParseError::UnexpectedEof { offset: 7, .. })
+    ));
+}
+
+#[test]
+fn invalid_utf8_is_reported_without_advancing() {
+    let mut reader = ByteReader::new(&[b'a', 0xff, 0]);
+
+    assert_eq!(
+        reader.read_cstr().unwrap_err(),
+        ParseError::InvalidUtf8 { offset: 1 }
+    );
+    assert_eq!(reader.position(), 0);
+}
+
+#[test]
+fn negative_one_length_is_null() {
+    let mut reader = ByteReader::new(&[
+        0xff, 0xff, 0xff, 0xff, 0, 0, 0, 1, 9, 0xff, 0xff, 0xff, 0xfe,
+    ]);
+
+    assert_eq!(reader.read_nullable_bytes().unwrap(), None);
+    assert_eq!(reader.read_nullable_bytes().unwrap(), Some(&[9][..]));
+    assert_eq!(
+        reader.read_nullable_bytes().unwrap_err(),
+        ParseError::InvalidLength {
+            offset: 9,
+            length: -2
+        }
+    );
+    assert_eq!(reader.position(), 9);
+}
@@ -0,0 +1,61 @@
+use postgres_replication_types::*;
+
+fn stream_abort(subtransaction_id: u32) -> ReplicationMessage<'static> {
+    ReplicationMessage::StreamAbort(StreamAbortMessage {
+        transaction_id: Xid(5),
+        subtransaction_id: Xid(subtransaction_id),
+        abort_lsn: None,
+        abort_timestamp: None,
+    })
+}
+
+#[test]
+fn transaction_boundaries() {
+    let timestamp = pg_timestamp_from_micros(0);
+    let begin = ReplicationMessage::Begin(BeginMessage {
+        final_lsn: Lsn(1),
+        timestamp,
+        transaction_id: Xid(1),
+    });
+    let commit = ReplicationMessage::Commit(CommitMessage {
+        lsn: Lsn(1),
+        final_lsn: Lsn(2),
+        timestamp,
+    });
+    let stream_commit = ReplicationMessage::StreamCommit(StreamCommitMessage {
+        transaction_id: Xid(5),
+        lsn: Lsn(1),
+        final_lsn: Lsn(2),
+        timestamp,
+    });
+
+    for message in [begin, commit, stream_commit, stream_abort(5)] {
+        assert!(message.is_transaction_boundary(), "{message}");
+        assert!(!message.is_data_change(), "{message}");
+    }
+
+    // Aborting a subtransaction doesn't end the transaction.
+    assert!(!stream_abort(6).is_transaction_boundary());
+    assert!(!ReplicationMessage::StreamStop(StreamStopMessage {}).is_transaction_boundary());
+}
+
+#[test]
+fn data_changes() {
+    let insert = ReplicationMessage::Insert(
+        InsertMessage::builder()
+            .oid(1)
+            .data(TupleData::builder().build())
+            .build(),
+    );
+    let truncate = ReplicationMessage::Truncate(TruncateMessage {
+        transaction_id: None,
+        is_cascade: false,
+        is_restart_identity: false,
+        oids: vec![1],
+    });
+
+    for message in [insert, truncate] {
+        assert!(message.is_data_change(), "{message}");
+        assert!(!message.is_transaction_boundary(), "{message}");
+    }
+}
@@ -0,0 +1,121 @@
+#![cfg(feature = "tokio-codec")]
+
+use bytes::BytesMut;
+use postgres_replication_types::*;
+use tokio_util::codec::Decoder;
+
+fn stream() -> Vec<u8> {
+    let mut buf = Vec::new();
+    ReplicationMessage::Begin(BeginMessage {
+        final_lsn: Lsn(0x016b_3778),
+        timestamp: pg_timestamp_from_micros(0),
+        transaction_id: Xid(735),
+    })
+    .encode(&mut buf)
+    .unwrap();
+    ReplicationMessage::Insert(
+        InsertMessage::builder()
+            .oid(16385)
+            .data(TupleData::builder().text("alice").build())
+            .build(),
+    )
+    .encode(&mut buf)
+    .unwrap();
+
+    buf
+}
+
+#[test]
+fn messages_are_decoded_a_byte_at_a_time() {
+    let mut decoder = ReplicationDecoder::new();
+    let mut src = BytesMut::new();
+    let mut messages = Vec::new();
+    for byte in stream() {
+        src.extend_from_slice(&[byte]);
+        while let Some(message) = decoder.decode(&mut src).unwrap() {
+            messages.push(message);
+        }
+    }
+
+    let [OwnedReplicationMessage::Begin(begin), OwnedReplicationMessage::Insert(insert)] =
+        messages.as_slice()
+    else {
+        panic!("unexpected messages: {messages:?}");
+    };
+    assert_eq!(begin.transaction_id, Xid(735));
+    assert_eq!(insert.data.columns[0].as_text(), Some("alice"));
+    assert!(src.is_empty());
+}
+
+#[test]
+fn cut_off_message_is_reported_at_eof() {
+    let mut decoder = ReplicationDecoder::new();
+    let mut src = BytesMut::from(&stream()[..3]);
+
+    assert_eq!(decoder.decode(&mut src).unwrap(), None);
+    assert!(matches!(
+        decoder.decode_eof(&mut src),
+        Err(ParseError::UnexpectedEof { .. })
+    ));
+}
+
+fn parallel_streaming(strict: bool) -> ReplicationDecoder {
+    ReplicationDecoder::with_options(
+        ParseOptions::default()
+            .with_version(ProtocolVersion::V4)
+            .strict(strict)
+            .parallel_streaming(true),
+    )
+}
+
+/// A `StreamAbort` with the abort LSN and timestamp of parallel streaming.
+fn stream_abort() -> Vec<u8> {
+    let mut buf = vec![b'A', 0, 0, 2, 0, 0, 0, 2, 1];
+    buf.extend_from_slice(&0x016b_3748u64.to_be_bytes());
+    buf.extend_from_slice(&1_000_000i64.to_be_bytes());
+    buf
+}
+
+#[test]
+fn strict_decoder_waits_for_the_abort_fields() {
+    let buf = stream_abort();
+    let mut decoder = parallel_streaming(true);
+    let mut src = BytesMut::from(&buf[..9]);
+
+    assert_eq!(decoder.decode(&mut src).unwrap(), None);
+
+    src.extend_from_slice(&buf[9..]);
+    let Some(OwnedReplicationMessage::StreamAbort(abort)) = decoder.decode(&mut src).unwrap()
+    else {
+        panic!("expected a stream abort");
+    };
+    assert_eq!(abort.abort_lsn, Some(Lsn(0x016b_3748)));
+    assert_eq!(
+        abort.abort_timestamp,
+        Some(pg_timestamp_from_micros(1_000_000))
+    );
+    assert!(src.is_empty());
+}
+
+#[test]
+fn tolerant_decoder_decodes_the_abort_without_missing_fields() {
+    let buf = stream_abort();
+    let mut decoder = parallel_streaming(false);
+    let mut src = BytesMut::from(&buf[..9]);
+
+    let Some(OwnedReplicationMessage::StreamAbort(abort)) = decoder.decode(&mut src).unwrap()
+    else {
+        panic!("expected a stream abort");
+    };
+    assert_eq!(abort.abort_lsn, None);
+    assert_eq!(abort.abort_timestamp, None);
+    assert!(src.is_empty());
+
+    // Had they been buffered, the fields would have been decoded.
+    let mut src = BytesMut::from(&buf[..]);
+    let Some(OwnedReplicationMessage::StreamAbort(abort)) = decoder.decode(&mut src).unwrap()
+    else {
+        panic!("expected a stream abort");
+    };
+    assert_eq!(abort.abort_lsn, Some(Lsn(0x016b_3748)));
+}
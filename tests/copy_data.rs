@@ -0,0 +1,55 @@
+use postgres_replication_types::*;
+
+/// A keepalive of a server at `0/16B3748`, requesting a reply.
+const KEEPALIVE: [u8; 18] = [
+    b'k', 0, 0, 0, 0, 0x01, 0x6b, 0x37, 0x48, 0x00, 0x02, 0xb0, 0xd9, 0x9c, 0x1d, 0x5c, 0x00, 1,
+];
+
+#[test]
+fn keepalive_is_parsed() {
+    let CopyData::PrimaryKeepalive(keepalive) = CopyData::parse(&KEEPALIVE).unwrap() else {
+        panic!("expected a keepalive");
+    };
+
+    assert_eq!(keepalive.wal_end, "0/16B3748".parse().unwrap());
+    assert_eq!(
+        keepalive.clock,
+        pg_timestamp_from_micros(0x0002_b0d9_9c1d_5c00)
+    );
+    assert!(keepalive.reply_requested);
+}
+
+#[test]
+fn keepalive_with_trailing_bytes_is_rejected() {
+    let mut buf = KEEPALIVE.to_vec();
+    buf.extend_from_slice(&[0, 0]);
+
+    assert_eq!(
+        CopyData::parse(&buf).unwrap_err(),
+        ParseError::TrailingBytes {
+            offset: 18,
+            count: 2
+        }
+    );
+}
+
+#[test]
+fn standby_status_update_matches_capture() {
+    // Sent by pg_recvlogical after receiving WAL up to `0/16B3748`.
+    let capture: [u8; StandbyStatusUpdate::LEN] = [
+        b'r', 0, 0, 0, 0, 0x01, 0x6b, 0x37, 0x48, 0, 0, 0, 0, 0x01, 0x6b, 0x37, 0x48, 0, 0, 0, 0,
+        0x01, 0x6b, 0x37, 0x10, 0x00, 0x02, 0xb0, 0xd9, 0x9c, 0x1d, 0x5c, 0x00, 0,
+    ];
+
+    let mut buf = Vec::new();
+    StandbyStatusUpdate {
+        written_lsn: "0/16B3748".parse().unwrap(),
+        flushed_lsn: "0/16B3748".parse().unwrap(),
+        applied_lsn: "0/16B3710".parse().unwrap(),
+        clock: pg_timestamp_from_micros(0x0002_b0d9_9c1d_5c00),
+        reply_requested: false,
+    }
+    .encode(&mut buf);
+
+    assert_eq!(buf, capture);
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "debezium")]
+
+use postgres_replication_types::*;
+use serde_json::json;
+
+fn relation() -> RelationMessage {
+    RelationMessage::builder()
+        .oid(16385)
+        .namespace(Some("public"))
+        .name("users")
+        .column("name", TEXT_OID)
+        .key_column("id", INT4_OID)
+        .build()
+}
+
+#[test]
+fn insert_values_are_typed() {
+    let event = ChangeEvent::from(
+        InsertMessage::builder()
+            .oid(16385)
+            .data(TupleData::builder().text("alice").text("1").build())
+            .build(),
+    );
+
+    let json = event.to_debezium_json(&relation());
+    assert_eq!(json["op"], "c");
+    assert_eq!(json["before"], json!(null));
+    assert_eq!(json["after"], json!({ "name": "alice", "id": 1 }));
+}
+
+#[test]
+fn key_only_before_is_named_after_the_key_columns() {
+    let event = ChangeEvent::from(
+        DeleteMessage::builder()
+            .oid(16385)
+            .key(TupleData::builder().text("1").build())
+            .build(),
+    );
+
+    let json = event.to_debezium_json(&relation());
+    assert_eq!(json["op"], "d");
+    assert_eq!(json["before"], json!({ "id": 1 }));
+}
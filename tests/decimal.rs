@@ -0,0 +1,81 @@
+#![cfg(feature = "decimal")]
+
+use postgres_replication_types::*;
+
+fn text(value: &str) -> TupleDataColumn<'_> {
+    TupleDataColumn {
+        kind: TupleDataKind::Text(value.into()),
+    }
+}
+
+fn binary(value: &[u8]) -> TupleDataColumn<'_> {
+    TupleDataColumn {
+        kind: TupleDataKind::Binary(value),
+    }
+}
+
+/// A binary `numeric` of base 10000 `digits`.
+fn numeric(weight: i16, sign: u16, dscale: u16, digits: &[u16]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    for word in [digits.len() as u16, weight as u16, sign, dscale] {
+        buf.extend_from_slice(&word.to_be_bytes());
+    }
+    for digit in digits {
+        buf.extend_from_slice(&digit.to_be_bytes());
+    }
+
+    buf
+}
+
+#[test]
+fn scale_is_kept() {
+    assert_eq!(
+        text("123.4500").as_decimal().unwrap().to_string(),
+        "123.4500"
+    );
+    assert_eq!(
+        binary(&numeric(0, 0, 4, &[123, 4500]))
+            .as_decimal()
+            .unwrap()
+            .to_string(),
+        "123.4500"
+    );
+    assert_eq!(
+        binary(&numeric(-1, 0, 4, &[1]))
+            .as_decimal()
+            .unwrap()
+            .to_string(),
+        "0.0001"
+    );
+}
+
+#[test]
+fn values_larger_than_i64_are_decoded() {
+    let large = binary(&numeric(5, 0, 0, &[123, 4567, 8901, 2345, 6789, 123]))
+        .as_decimal()
+        .unwrap();
+
+    assert_eq!(large.to_string(), "12345678901234567890123");
+    assert_eq!(text("12345678901234567890123").as_decimal().unwrap(), large);
+}
+
+#[test]
+fn negative_values_are_decoded() {
+    assert_eq!(text("-5.25").as_decimal().unwrap().to_string(), "-5.25");
+    assert_eq!(
+        binary(&numeric(0, 0x4000, 2, &[5]))
+            .as_decimal()
+            .unwrap()
+            .to_string(),
+        "-5.00"
+    );
+}
+
+#[test]
+fn nan_is_rejected() {
+    assert!(matches!(
+        text("NaN").as_decimal(),
+        Err(ValueDecodeError::ParseDecimal(_))
+    ));
+    assert!(binary(&numeric(0, 0xC000, 0, &[])).as_decimal().is_err());
+}
@@ -0,0 +1,53 @@
+use postgres_replication_types::*;
+
+#[test]
+fn messages_are_summarized_on_one_line() {
+    let insert = ReplicationMessage::Insert(
+        InsertMessage::builder()
+            .oid(16385)
+            .data(TupleData::builder().null().null().null().build())
+            .build(),
+    );
+    assert_eq!(insert.to_string(), "INSERT rel=16385 cols=3");
+
+    let commit = ReplicationMessage::Commit(CommitMessage {
+        lsn: Lsn(0x16_B374_D848),
+        final_lsn: Lsn(0),
+        timestamp: pg_timestamp_from_micros(0),
+    });
+    assert_eq!(commit.to_string(), "COMMIT lsn=16/B374D848");
+
+    let truncate = ReplicationMessage::Truncate(TruncateMessage {
+        transaction_id: Some(Xid(7)),
+        is_cascade: true,
+        is_restart_identity: false,
+        oids: vec![1, 2],
+    });
+    assert_eq!(truncate.to_string(), "TRUNCATE rels=1,2 cascade xid=7");
+
+    let generic = ReplicationMessage::Generic(GenericMessage {
+        transaction_id: None,
+        is_transactional: false,
+        lsn: Lsn(1),
+        prefix: "p".into(),
+        length: 3,
+        content: b"abc",
+    });
+    let relation = ReplicationMessage::Relation(
+        RelationMessage::builder()
+            .oid(16385)
+            .namespace(Some("my.schema"))
+            .name("user\nlog")
+            .key_column("id", INT4_OID)
+            .build(),
+    );
+    assert_eq!(
+        relation.to_string(),
+        r#"RELATION rel=16385 name="my.schema"."user\nlog" cols=1"#
+    );
+
+    assert_eq!(
+        generic.to_string(),
+        r#"MESSAGE lsn=0/1 prefix="p" len=3 transactional=false"#
+    );
+}
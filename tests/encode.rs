@@ -0,0 +1,59 @@
+use postgres_replication_types::*;
+
+fn insert(columns: usize) -> ReplicationMessage<'static> {
+    let data = (0..columns).fold(TupleData::builder(), |data, _| data.null());
+
+    ReplicationMessage::Insert(
+        InsertMessage::builder()
+            .oid(16385)
+            .data(data.build())
+            .build(),
+    )
+}
+
+#[test]
+fn too_many_columns_are_rejected_without_writing() {
+    let mut buf = b"prefix".to_vec();
+
+    assert_eq!(
+        insert(32_768).encode(&mut buf),
+        Err(EncodeError::TooManyColumns { count: 32_768 })
+    );
+    assert_eq!(buf, b"prefix");
+
+    let owned = insert(32_768).into_owned();
+    assert_eq!(
+        owned.encode(&mut buf),
+        Err(EncodeError::TooManyColumns { count: 32_768 })
+    );
+    assert_eq!(buf, b"prefix");
+}
+
+#[test]
+fn borrowed_and_owned_tuples_encode_the_same() {
+    let message = ReplicationMessage::Insert(
+        InsertMessage::builder()
+            .oid(16385)
+            .data(TupleData::builder().text("42").null().build())
+            .build(),
+    );
+
+    let mut borrowed = Vec::new();
+    message.encode(&mut borrowed).unwrap();
+    let mut owned = Vec::new();
+    message.into_owned().encode(&mut owned).unwrap();
+
+    assert_eq!(borrowed, owned);
+    assert_eq!(
+        borrowed,
+        [b'I', 0, 0, 0x40, 0x01, b'N', 0, 2, b't', 0, 0, 0, 2, b'4', b'2', b'n']
+    );
+}
+
+#[test]
+fn max_columns_are_encoded() {
+    let mut buf = Vec::new();
+    insert(32_767).encode(&mut buf).unwrap();
+
+    assert_eq!(buf[6..8], 32_767_i16.to_be_bytes());
+}
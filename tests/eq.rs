@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use postgres_replication_types::*;
+
+fn begin(timestamp: &str) -> ReplicationMessage<'static> {
+    let timestamp: DateTime<Utc> = timestamp.parse().unwrap();
+
+    ReplicationMessage::Begin(BeginMessage {
+        final_lsn: Lsn(1),
+        timestamp,
+        transaction_id: Xid(1),
+    })
+}
+
+#[test]
+fn timestamps_are_compared_in_microseconds() {
+    assert_eq!(
+        begin("2024-01-01T00:00:00.123456001Z"),
+        begin("2024-01-01T00:00:00.123456999Z")
+    );
+    assert_ne!(
+        begin("2024-01-01T00:00:00.123456999Z"),
+        begin("2024-01-01T00:00:00.123457Z")
+    );
+}
@@ -0,0 +1,20 @@
+use postgres_replication_types::*;
+
+#[test]
+fn display_and_from_str_round_trip() {
+    let lsn: Lsn = "16/B374D848".parse().unwrap();
+    assert_eq!(lsn, Lsn(0x16_B374_D848));
+    assert_eq!(lsn.to_string(), "16/B374D848");
+    assert_eq!(Lsn(0).to_string(), "0/0");
+    assert_eq!(
+        Lsn(u64::MAX).to_string().parse::<Lsn>().unwrap(),
+        Lsn(u64::MAX)
+    );
+}
+
+#[test]
+fn malformed_text_is_rejected() {
+    for text in ["16B374D848", "/1", "1/", "1/123456789", "g/0", "1/2/3"] {
+        assert_eq!(text.parse::<Lsn>(), Err(ParseLsnError), "{text}");
+    }
+}
@@ -0,0 +1,47 @@
+use postgres_replication_types::*;
+
+fn users() -> RelationMessage {
+    RelationMessage::builder()
+        .oid(16385)
+        .namespace(Some("public"))
+        .name("users")
+        .key_column("id", 23)
+        .column("email", 25)
+        .column("name", 25)
+        .build()
+}
+
+#[test]
+fn columns_are_looked_up_by_name() {
+    let relation = users();
+    let row = TupleData::builder()
+        .text("42")
+        .text("alice@example.com")
+        .null()
+        .build();
+
+    let map = row.as_map(&relation);
+    assert_eq!(map.len(), 3);
+    assert_eq!(map["email"].as_text(), Some("alice@example.com"));
+    assert_eq!(map["id"].as_i32().unwrap(), 42);
+    assert!(map["name"].is_null());
+    assert!(!map.contains_key("missing"));
+
+    let map = row.as_hash_map(&relation);
+    assert_eq!(map["email"].as_text(), Some("alice@example.com"));
+    assert!(map["name"].is_null());
+}
+
+#[test]
+fn the_first_of_duplicate_columns_is_kept() {
+    let relation = RelationMessage::builder()
+        .oid(16385)
+        .name("users")
+        .column("email", 25)
+        .column("email", 25)
+        .build();
+    let row = TupleData::builder().text("first").text("second").build();
+
+    assert_eq!(row.as_map(&relation)["email"].as_text(), Some("first"));
+    assert_eq!(row.as_hash_map(&relation)["email"].as_text(), Some("first"));
+}
@@ -0,0 +1,60 @@
+use postgres_replication_types::*;
+
+fn row() -> TupleData<'static> {
+    TupleData::builder().text("1").build()
+}
+
+#[test]
+fn op_of_each_source_message() {
+    let insert = InsertMessage::builder().oid(1).data(row()).build();
+    assert_eq!(
+        ReplicationMessage::Insert(insert.clone()).op(),
+        Some(Op::Insert)
+    );
+    assert_eq!(ChangeEvent::from(insert).op, Op::Insert);
+
+    let update = UpdateMessage::builder().oid(1).new_tuple(row()).build();
+    assert_eq!(
+        ReplicationMessage::Update(update.clone()).op(),
+        Some(Op::Update)
+    );
+    assert_eq!(ChangeEvent::from(update).op, Op::Update);
+
+    let delete = DeleteMessage::builder().oid(1).key(row()).build();
+    assert_eq!(
+        ReplicationMessage::Delete(delete.clone()).op(),
+        Some(Op::Delete)
+    );
+    assert_eq!(ChangeEvent::from(delete).op, Op::Delete);
+
+    let truncate = TruncateMessage {
+        transaction_id: None,
+        is_cascade: true,
+        is_restart_identity: false,
+        oids: vec![1, 2],
+    };
+    assert_eq!(
+        ReplicationMessage::Truncate(truncate.clone()).op(),
+        Some(Op::Truncate)
+    );
+    let event = TruncateEvent::from(truncate);
+    assert_eq!(event.op(), Op::Truncate);
+    assert_eq!(event.relation_oids, [1, 2]);
+}
+
+#[test]
+fn messages_without_changes_have_no_op() {
+    assert_eq!(
+        ReplicationMessage::StreamStop(StreamStopMessage {}).op(),
+        None
+    );
+    assert_eq!(
+        ReplicationMessage::Commit(CommitMessage {
+            lsn: Lsn(1),
+            final_lsn: Lsn(2),
+            timestamp: pg_timestamp_from_micros(0),
+        })
+        .op(),
+        None
+    );
+}
@@ -0,0 +1,23 @@
+use postgres_replication_types::*;
+
+fn parse_owned() -> OwnedReplicationMessage {
+    let buf = vec![
+        b'I', 0, 0, 0x40, 0x01, b'N', 0, 2, b't', 0, 0, 0, 2, b'h', b'i', b'b', 0, 0, 0, 1, 9,
+    ];
+
+    ReplicationMessage::parse(&buf).unwrap().to_owned()
+}
+
+#[test]
+fn owned_message_outlives_the_buffer() {
+    fn assert_static<T: Send + 'static>(message: T) -> T {
+        message
+    }
+
+    let OwnedReplicationMessage::Insert(insert) = assert_static(parse_owned()) else {
+        panic!("expected an insert");
+    };
+    assert_eq!(insert.oid, 16385);
+    assert_eq!(insert.data.columns[0].as_text(), Some("hi"));
+    assert_eq!(insert.data.columns[1].as_binary(), Some(&[9][..]));
+}
@@ -0,0 +1,461 @@
+use postgres_replication_types::*;
+
+fn v4(parallel_streaming: bool) -> ParseOptions {
+    ParseOptions::default()
+        .with_version(ProtocolVersion::V4)
+        .parallel_streaming(parallel_streaming)
+}
+
+/// A `StreamAbort` without the parallel fields, followed by a `StreamStop`.
+const ABORT_THEN_STOP: &[u8] = &[b'A', 0, 0, 2, 0, 0, 0, 2, 1, b'E'];
+
+#[test]
+fn stream_abort_without_parallel_streaming_leaves_the_next_message() {
+    let options = v4(false);
+    let messages = MessageIter::with_options(ABORT_THEN_STOP, options)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+
+    let [ReplicationMessage::StreamAbort(abort), ReplicationMessage::StreamStop(_)] =
+        messages.as_slice()
+    else {
+        panic!("unexpected messages: {messages:?}");
+    };
+    assert_eq!(abort.transaction_id, Xid(512));
+    assert_eq!(abort.subtransaction_id, Xid(513));
+    assert_eq!(abort.abort_lsn, None);
+    assert_eq!(abort.abort_timestamp, None);
+
+    let (_, consumed) =
+        ReplicationMessage::parse_prefix_with_options(ABORT_THEN_STOP, options).unwrap();
+    assert_eq!(consumed, 9);
+    assert_eq!(
+        ReplicationMessage::skip_with_options(ABORT_THEN_STOP, options).unwrap(),
+        9
+    );
+}
+
+#[test]
+fn stream_abort_with_parallel_streaming_reads_the_abort_fields() {
+    let mut buf = vec![b'A', 0, 0, 2, 0, 0, 0, 2, 1];
+    buf.extend_from_slice(&0x16_B374_D848u64.to_be_bytes());
+    buf.extend_from_slice(&1_000_000i64.to_be_bytes());
+    buf.push(b'E');
+
+    let (message, consumed) =
+        ReplicationMessage::parse_prefix_with_options(&buf, v4(true)).unwrap();
+    let ReplicationMessage::StreamAbort(abort) = message else {
+        panic!("unexpected message: {message:?}");
+    };
+    assert_eq!(consumed, 25);
+    assert_eq!(abort.abort_lsn, Some(Lsn(0x16_B374_D848)));
+    assert_eq!(
+        abort.abort_timestamp,
+        Some(pg_timestamp_from_micros(1_000_000))
+    );
+    assert_eq!(
+        ReplicationMessage::skip_with_options(&buf, v4(true)).unwrap(),
+        25
+    );
+
+    // Before version 4 the fields are never sent.
+    let v3 = v4(true).with_version(ProtocolVersion::V3);
+    assert_eq!(
+        ReplicationMessage::parse_prefix_with_options(&buf, v3)
+            .unwrap()
+            .1,
+        9
+    );
+}
+
+#[test]
+fn stream_abort_with_cut_off_parallel_fields() {
+    let buf = &ABORT_THEN_STOP[..9];
+
+    assert!(matches!(
+        ReplicationMessage::parse_with_options(buf, v4(true)),
+        Err(ParseError::UnexpectedEof { offset: 9, .. })
+    ));
+
+    let tolerant = v4(true).strict(false);
+    let ReplicationMessage::StreamAbort(abort) =
+        ReplicationMessage::parse_with_options(buf, tolerant).unwrap()
+    else {
+        panic!("expected a stream abort");
+    };
+    assert_eq!(abort.abort_lsn, None);
+}
+
+#[test]
+fn negative_column_counts_are_rejected() {
+    // An insert of a tuple with -1 columns, followed by bytes that would be its columns.
+    let insert = [b'I', 0, 0, 0x40, 0x01, b'N', 0xff, 0xff, b'n', b'n'];
+    let expected = ParseError::InvalidLength {
+        offset: 6,
+        length: -1,
+    };
+    assert_eq!(ReplicationMessage::parse(&insert).unwrap_err(), expected);
+    assert_eq!(ReplicationMessage::skip(&insert).unwrap_err(), expected);
+
+    let mut relation = vec![b'R', 0, 0, 0x40, 0x01];
+    relation.extend_from_slice(b"public\0users\0d");
+    relation.extend_from_slice(&(-2i16).to_be_bytes());
+    let expected = ParseError::InvalidLength {
+        offset: 19,
+        length: -2,
+    };
+    assert_eq!(ReplicationMessage::parse(&relation).unwrap_err(), expected);
+    assert_eq!(ReplicationMessage::skip(&relation).unwrap_err(), expected);
+}
+
+/// A `Begin` of `INSERT INTO users VALUES (1, 'alice')`, captured from pgoutput.
+const BEGIN: &[u8] = &[
+    b'B', 0, 0, 0, 0, 0x01, 0x6b, 0x37, 0x78, 0x00, 0x02, 0xb0, 0xd9, 0x9c, 0x1d, 0x5c, 0x00, 0, 0,
+    0x02, 0xdf,
+];
+
+/// The `Insert` of the transaction.
+const INSERT: &[u8] = &[
+    b'I', 0, 0, 0x40, 0x01, b'N', 0, 2, b't', 0, 0, 0, 1, b'1', b't', 0, 0, 0, 5, b'a', b'l', b'i',
+    b'c', b'e',
+];
+
+/// The `Commit` of the transaction.
+const COMMIT: &[u8] = &[
+    b'C', 0, 0, 0, 0, 0, 0x01, 0x6b, 0x37, 0x48, 0, 0, 0, 0, 0x01, 0x6b, 0x37, 0x78, 0x00, 0x02,
+    0xb0, 0xd9, 0x9c, 0x1d, 0x5c, 0x00,
+];
+
+#[test]
+fn captured_transaction_round_trips() {
+    let ReplicationMessage::Begin(begin) = ReplicationMessage::parse(BEGIN).unwrap() else {
+        panic!("expected a begin");
+    };
+    assert_eq!(begin.final_lsn, Lsn(0x016b_3778));
+    assert_eq!(
+        begin.timestamp,
+        pg_timestamp_from_micros(0x0002_b0d9_9c1d_5c00)
+    );
+    assert_eq!(begin.transaction_id, Xid(735));
+
+    let ReplicationMessage::Insert(insert) = ReplicationMessage::parse(INSERT).unwrap() else {
+        panic!("expected an insert");
+    };
+    assert_eq!(insert.oid, 16385);
+    assert_eq!(insert.data.columns[0].as_text(), Some("1"));
+    assert_eq!(insert.data.columns[1].as_text(), Some("alice"));
+
+    let ReplicationMessage::Commit(commit) = ReplicationMessage::parse(COMMIT).unwrap() else {
+        panic!("expected a commit");
+    };
+    assert_eq!(commit.lsn, Lsn(0x016b_3748));
+    assert_eq!(commit.final_lsn, begin.final_lsn);
+
+    for capture in [BEGIN, INSERT, COMMIT] {
+        let mut buf = Vec::new();
+        ReplicationMessage::parse(capture)
+            .unwrap()
+            .encode(&mut buf)
+            .unwrap();
+        assert_eq!(buf, capture);
+    }
+}
+
+#[test]
+fn truncated_buffers_report_the_offset() {
+    assert_eq!(
+        ReplicationMessage::parse(&BEGIN[..3]).unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 1,
+            needed: 8,
+            remaining: 2
+        }
+    );
+    assert_eq!(
+        ReplicationMessage::parse(&INSERT[..16]).unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 15,
+            needed: 4,
+            remaining: 1
+        }
+    );
+    assert_eq!(
+        ReplicationMessage::parse(&INSERT[..21]).unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 19,
+            needed: 5,
+            remaining: 2
+        }
+    );
+    assert_eq!(
+        ReplicationMessage::parse(&[b'O', 0, 0, 0, 0, 0, 0, 0, 0, b'a']).unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 9,
+            needed: 2,
+            remaining: 1
+        }
+    );
+    assert_eq!(
+        ReplicationMessage::parse(&[]).unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 0,
+            needed: 1,
+            remaining: 0
+        }
+    );
+
+    for capture in [BEGIN, INSERT, COMMIT] {
+        for len in 0..capture.len() {
+            assert!(matches!(
+                ReplicationMessage::parse(&capture[..len]),
+                Err(ParseError::UnexpectedEof { .. })
+            ));
+        }
+    }
+}
+
+#[test]
+fn invalid_bytes_report_the_offset() {
+    assert_eq!(
+        ReplicationMessage::parse(b"Z").unwrap_err(),
+        ParseError::UnknownMessageType(b'Z')
+    );
+
+    let mut insert = INSERT.to_vec();
+    insert[8] = b'x';
+    assert_eq!(
+        ReplicationMessage::parse(&insert).unwrap_err(),
+        ParseError::InvalidTupleColumnKind {
+            offset: 8,
+            kind: b'x'
+        }
+    );
+
+    let mut insert = INSERT.to_vec();
+    insert[20] = 0xff;
+    assert_eq!(
+        ReplicationMessage::parse(&insert).unwrap_err(),
+        ParseError::InvalidUtf8 { offset: 20 }
+    );
+}
+
+#[test]
+fn truncate_of_three_relations() {
+    let mut buf = vec![b'T', 0, 0, 0, 3, 3];
+    for oid in [16385i32, 16390, 16395] {
+        buf.extend_from_slice(&oid.to_be_bytes());
+    }
+
+    let ReplicationMessage::Truncate(truncate) = ReplicationMessage::parse(&buf).unwrap() else {
+        panic!("expected a truncate");
+    };
+    assert_eq!(truncate.oids, [16385, 16390, 16395]);
+    assert_eq!(truncate.relations_count(), 3);
+    assert!(truncate.is_cascade);
+    assert!(truncate.is_restart_identity);
+
+    assert_eq!(
+        ReplicationMessage::parse(&buf[..buf.len() - 1]).unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 14,
+            needed: 4,
+            remaining: 3
+        }
+    );
+}
+
+#[test]
+fn streamed_xid_depends_on_the_version() {
+    // The insert of a streamed transaction, with the xid before the OID.
+    let mut streamed = vec![b'I', 0, 0, 0x03, 0x09];
+    streamed.extend_from_slice(&INSERT[1..]);
+
+    let ReplicationMessage::Insert(insert) =
+        ReplicationMessage::parse_with_version(&streamed, ProtocolVersion::V2).unwrap()
+    else {
+        panic!("expected an insert");
+    };
+    assert_eq!(insert.transaction_id, Some(Xid(777)));
+    assert_eq!(insert.oid, 16385);
+
+    // Version 1 reads the xid as the OID, and the OID as the tuple.
+    assert_eq!(
+        ReplicationMessage::parse_with_version(&streamed, ProtocolVersion::V1).unwrap_err(),
+        ParseError::InvalidTupleType { offset: 5, tag: 0 }
+    );
+
+    let ReplicationMessage::Insert(insert) =
+        ReplicationMessage::parse_with_version(INSERT, ProtocolVersion::V1).unwrap()
+    else {
+        panic!("expected an insert");
+    };
+    assert_eq!(insert.transaction_id, None);
+    assert_eq!(insert.oid, 16385);
+}
+
+#[test]
+fn message_iter_parses_consecutive_messages() {
+    let buf = [BEGIN, INSERT, COMMIT].concat();
+
+    let messages = MessageIter::new(&buf)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert!(matches!(
+        messages.as_slice(),
+        [
+            ReplicationMessage::Begin(_),
+            ReplicationMessage::Insert(_),
+            ReplicationMessage::Commit(_)
+        ]
+    ));
+
+    // A cut off message is reported once, without moving past it.
+    let mut iter = MessageIter::new(&buf[..buf.len() - 3]);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(iter.next().unwrap().is_ok());
+    let position = iter.position();
+    assert_eq!(position, BEGIN.len() + INSERT.len());
+    assert!(matches!(
+        iter.next(),
+        Some(Err(ParseError::UnexpectedEof { .. }))
+    ));
+    assert_eq!(iter.position(), position);
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn parse_prefix_reports_the_consumed_length() {
+    let buf = [INSERT, b"E"].concat();
+
+    let (message, consumed) = ReplicationMessage::parse_prefix(&buf).unwrap();
+    assert!(matches!(message, ReplicationMessage::Insert(_)));
+    assert_eq!(consumed, 24);
+    assert_eq!(consumed, INSERT.len());
+
+    let (message, consumed) = ReplicationMessage::parse_prefix(&buf[consumed..]).unwrap();
+    assert!(matches!(message, ReplicationMessage::StreamStop(_)));
+    assert_eq!(consumed, 1);
+}
+
+/// A logical decoding message of prefix `p` at `0/9`.
+fn generic(flags: u8, length: i32, content: &[u8]) -> Vec<u8> {
+    let mut buf = vec![b'M', flags, 0, 0, 0, 0, 0, 0, 0, 9, b'p', 0];
+    buf.extend_from_slice(&length.to_be_bytes());
+    buf.extend_from_slice(content);
+    buf
+}
+
+#[test]
+fn transactional_and_non_transactional_generic_messages() {
+    for (flags, is_transactional) in [(1, true), (0, false)] {
+        let buf = generic(flags, 3, b"abc");
+        let ReplicationMessage::Generic(message) = ReplicationMessage::parse(&buf).unwrap() else {
+            panic!("expected a generic message");
+        };
+        assert_eq!(message.is_transactional, is_transactional);
+        assert_eq!(message.transaction_id, None);
+        assert_eq!(message.lsn, Lsn(9));
+        assert_eq!(message.prefix, "p");
+        assert_eq!(message.content, b"abc");
+    }
+
+    assert_eq!(
+        ReplicationMessage::parse(&generic(0, 3, b"ab")).unwrap_err(),
+        ParseError::UnexpectedEof {
+            offset: 16,
+            needed: 3,
+            remaining: 2
+        }
+    );
+
+    // Streamed, so with the xid after the tag.
+    let mut streamed = vec![b'M', 0, 0, 0, 5];
+    streamed.extend_from_slice(&generic(1, 1, b"x")[1..]);
+    let ReplicationMessage::Generic(message) =
+        ReplicationMessage::parse_with_version(&streamed, ProtocolVersion::V2).unwrap()
+    else {
+        panic!("expected a generic message");
+    };
+    assert_eq!(message.transaction_id, Some(Xid(5)));
+    assert!(message.is_transactional);
+}
+
+#[test]
+fn stream_stop_with_trailing_bytes() {
+    assert!(matches!(
+        ReplicationMessage::parse(b"E"),
+        Ok(ReplicationMessage::StreamStop(_))
+    ));
+
+    let error = ReplicationMessage::parse(b"Exyz").unwrap_err();
+    assert_eq!(
+        error,
+        ParseError::TrailingBytes {
+            offset: 1,
+            count: 3
+        }
+    );
+    assert_eq!(
+        error.to_string(),
+        "3 trailing bytes after the message at offset 1"
+    );
+
+    assert_eq!(ReplicationMessage::parse_prefix(b"Exyz").unwrap().1, 1);
+    assert!(ReplicationMessage::parse_tolerant(b"Exyz", ProtocolVersion::V1).is_ok());
+}
+
+#[test]
+fn truncate_flag_combinations() {
+    for (bits, cascade, restart_identity) in [
+        (0, false, false),
+        (1, true, false),
+        (2, false, true),
+        (3, true, true),
+    ] {
+        let buf = [b'T', 0, 0, 0, 1, bits, 0, 0, 0x40, 0x01];
+        let message = ReplicationMessage::parse(&buf).unwrap();
+        let ReplicationMessage::Truncate(truncate) = &message else {
+            panic!("expected a truncate");
+        };
+        assert_eq!(
+            truncate.options(),
+            TruncateOptions {
+                cascade,
+                restart_identity
+            }
+        );
+        assert_eq!(truncate.is_cascade, cascade);
+        assert_eq!(truncate.is_restart_identity, restart_identity);
+        assert_eq!(TruncateOptions::from_bits(bits).bits(), bits);
+
+        let mut encoded = Vec::new();
+        message.encode(&mut encoded).unwrap();
+        assert_eq!(encoded, buf);
+    }
+}
+
+#[test]
+fn negative_one_length_column_is_null() {
+    let mut buf = vec![b'I', 0, 0, 0x40, 0x01, b'N', 0, 3];
+    buf.extend_from_slice(&[b't', 0xff, 0xff, 0xff, 0xff]);
+    buf.extend_from_slice(&[b'b', 0xff, 0xff, 0xff, 0xff]);
+    buf.extend_from_slice(&[b't', 0, 0, 0, 1, b'x']);
+
+    let ReplicationMessage::Insert(insert) = ReplicationMessage::parse(&buf).unwrap() else {
+        panic!("expected an insert");
+    };
+    assert!(insert.data.columns[0].is_null());
+    assert!(insert.data.columns[1].is_null());
+    assert_eq!(insert.data.columns[2].as_text(), Some("x"));
+
+    let buf = [
+        b'I', 0, 0, 0x40, 0x01, b'N', 0, 1, b't', 0xff, 0xff, 0xff, 0xfe,
+    ];
+    assert_eq!(
+        ReplicationMessage::parse(&buf).unwrap_err(),
+        ParseError::InvalidLength {
+            offset: 9,
+            length: -2
+        }
+    );
+}
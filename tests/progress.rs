@@ -0,0 +1,60 @@
+use postgres_replication_types::*;
+
+fn clock() -> chrono::DateTime<chrono::Utc> {
+    pg_timestamp_from_micros(757_382_400_000_000)
+}
+
+fn xlog_data(wal_start: u64, wal_end: u64, payload: &[u8]) -> CopyData<'_> {
+    CopyData::XLogData(XLogData {
+        wal_start: Lsn(wal_start),
+        wal_end: Lsn(wal_end),
+        clock: clock(),
+        payload,
+    })
+}
+
+fn keepalive(wal_end: u64) -> CopyData<'static> {
+    CopyData::PrimaryKeepalive(PrimaryKeepalive {
+        wal_end: Lsn(wal_end),
+        clock: clock(),
+        reply_requested: false,
+    })
+}
+
+#[test]
+fn lag_follows_received_and_applied_frames() {
+    let mut progress = ReplicationProgress::starting_at(Lsn(1_000));
+    assert_eq!(progress.lag_bytes(), 0);
+
+    // The payload length doesn't move the received location.
+    progress.observe(&xlog_data(1_100, 1_500, &[b'B'; 21]));
+    assert_eq!(progress.last_received(), Lsn(1_100));
+    assert_eq!(progress.server_wal_end(), Lsn(1_500));
+    assert_eq!(progress.lag_bytes(), 500);
+
+    progress.observe(&xlog_data(1_200, 1_500, &[b'C'; 26]));
+    progress.applied(Lsn(1_200));
+    assert_eq!(progress.last_received(), Lsn(1_200));
+    assert_eq!(progress.last_applied(), Lsn(1_200));
+    assert_eq!(progress.lag_bytes(), 300);
+
+    progress.observe(&keepalive(2_000));
+    assert_eq!(progress.lag_bytes(), 800);
+
+    // Locations never move backwards.
+    progress.observe(&xlog_data(1_150, 1_400, &[]));
+    progress.observe(&keepalive(1_800));
+    progress.applied(Lsn(1_100));
+    assert_eq!(progress.last_received(), Lsn(1_200));
+    assert_eq!(progress.server_wal_end(), Lsn(2_000));
+    assert_eq!(progress.lag_bytes(), 800);
+
+    progress.applied(Lsn(2_000));
+    assert_eq!(progress.lag_bytes(), 0);
+
+    let update = progress.status_update(clock(), true);
+    assert_eq!(update.written_lsn, Lsn(2_000));
+    assert_eq!(update.flushed_lsn, Lsn(2_000));
+    assert_eq!(update.applied_lsn, Lsn(2_000));
+    assert!(update.reply_requested);
+}
@@ -0,0 +1,74 @@
+use postgres_replication_types::*;
+
+fn frame(message: ReplicationMessage<'_>) -> Vec<u8> {
+    let mut buf = Vec::new();
+    message.encode(&mut buf).unwrap();
+    buf
+}
+
+fn relation() -> RelationMessage {
+    RelationMessage::builder()
+        .oid(16385)
+        .namespace(Some("public"))
+        .name("users")
+        .key_column("id", 23)
+        .column("name", 25)
+        .build()
+}
+
+#[test]
+fn changes_are_resolved_against_the_replayed_relation() {
+    let timestamp = pg_timestamp_from_micros(0);
+    let frames = [
+        frame(ReplicationMessage::Begin(BeginMessage {
+            final_lsn: Lsn(11),
+            timestamp,
+            transaction_id: Xid(7),
+        })),
+        frame(ReplicationMessage::Relation(relation())),
+        frame(ReplicationMessage::Insert(
+            InsertMessage::builder()
+                .oid(16385)
+                .data(TupleData::builder().text("1").text("ann").build())
+                .build(),
+        )),
+        frame(ReplicationMessage::Commit(CommitMessage {
+            lsn: Lsn(10),
+            final_lsn: Lsn(11),
+            timestamp,
+        })),
+    ];
+
+    let mut stream = ReplicationStream::new();
+    let events = frames
+        .iter()
+        .map(|frame| stream.push(frame).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        events.iter().map(Vec::len).collect::<Vec<_>>(),
+        [0, 0, 1, 0]
+    );
+
+    let insert = &events[2][0];
+    assert_eq!(insert.op, Op::Insert);
+    assert_eq!(insert.relation_name, "users");
+    let after = insert.after.as_ref().unwrap();
+    assert_eq!(after.get("id").unwrap().as_text(), Some("1"));
+    assert_eq!(after.get("name").unwrap().as_text(), Some("ann"));
+    assert!(stream.relations().get(16385).is_some());
+}
+
+#[test]
+fn change_of_an_unknown_relation_is_rejected() {
+    let insert = frame(ReplicationMessage::Insert(
+        InsertMessage::builder()
+            .oid(16385)
+            .data(TupleData::builder().text("1").text("ann").build())
+            .build(),
+    ));
+
+    assert_eq!(
+        ReplicationStream::new().push(&insert),
+        Err(ReplicationStreamError::UnknownRelation { oid: 16385 })
+    );
+}
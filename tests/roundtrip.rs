@@ -0,0 +1,323 @@
+use chrono::{DateTime, Utc};
+use postgres_replication_types::*;
+use proptest::collection::vec;
+use proptest::option;
+use proptest::prelude::*;
+
+/// Microseconds between the Unix epoch and the Postgres epoch.
+const PG_EPOCH_OFFSET_MICROS: i64 = 946_684_800_000_000;
+
+fn version() -> impl Strategy<Value = ProtocolVersion> {
+    prop_oneof![
+        Just(ProtocolVersion::V1),
+        Just(ProtocolVersion::V2),
+        Just(ProtocolVersion::V3),
+        Just(ProtocolVersion::V4),
+    ]
+}
+
+/// `streaming = parallel` can only be requested since version 4.
+fn options() -> impl Strategy<Value = ParseOptions> {
+    (version(), any::<bool>()).prop_map(|(version, parallel_streaming)| {
+        ParseOptions::default()
+            .with_version(version)
+            .parallel_streaming(parallel_streaming && version >= ProtocolVersion::V4)
+    })
+}
+
+/// Timestamps with microsecond precision, well within the range of chrono.
+fn timestamp() -> impl Strategy<Value = DateTime<Utc>> {
+    (-(1i64 << 62)..(1i64 << 62)).prop_map(|micros| {
+        DateTime::from_timestamp_micros(micros + PG_EPOCH_OFFSET_MICROS).unwrap()
+    })
+}
+
+fn lsn() -> impl Strategy<Value = Lsn> {
+    any::<u64>().prop_map(Lsn)
+}
+
+/// NUL-terminated strings on the wire can't contain NUL.
+fn string() -> impl Strategy<Value = String> {
+    "[^\u{0}]{0,16}"
+}
+
+fn namespace() -> impl Strategy<Value = Option<String>> {
+    option::of("[^\u{0}]{1,16}")
+}
+
+fn xid() -> impl Strategy<Value = Xid> {
+    any::<u32>().prop_map(Xid)
+}
+
+/// The xid of streamed messages is present exactly when the version carries it.
+fn streamed_xid(version: ProtocolVersion) -> BoxedStrategy<Option<Xid>> {
+    if version.has_streamed_xid() {
+        xid().prop_map(Some).boxed()
+    } else {
+        Just(None).boxed()
+    }
+}
+
+/// The abort LSN and timestamp of `StreamAbort` are present exactly with parallel streaming.
+fn parallel_abort(options: ParseOptions) -> BoxedStrategy<Option<(Lsn, DateTime<Utc>)>> {
+    if options.parallel_streaming {
+        (lsn(), timestamp()).prop_map(Some).boxed()
+    } else {
+        Just(None).boxed()
+    }
+}
+
+fn tuple() -> impl Strategy<Value = OwnedTupleData> {
+    let kind = prop_oneof![
+        Just(OwnedTupleDataKind::Null),
+        Just(OwnedTupleDataKind::UnchangedToast),
+        any::<String>().prop_map(OwnedTupleDataKind::Text),
+        vec(any::<u8>(), 0..16).prop_map(OwnedTupleDataKind::Binary),
+    ];
+
+    vec(kind.prop_map(|kind| OwnedTupleDataColumn { kind }), 0..8)
+        .prop_map(|columns| OwnedTupleData { columns })
+}
+
+fn relation(version: ProtocolVersion) -> impl Strategy<Value = RelationMessage> {
+    let replica_identity = prop_oneof![
+        Just(ReplicaIdentity::Default),
+        Just(ReplicaIdentity::Nothing),
+        Just(ReplicaIdentity::Full),
+        Just(ReplicaIdentity::Index),
+    ];
+    let column = (any::<bool>(), string(), any::<i32>(), any::<i32>()).prop_map(
+        |(is_part_of_the_key, name, oid, type_modifier)| RelationMessageColumn {
+            is_part_of_the_key,
+            name,
+            oid,
+            type_modifier,
+        },
+    );
+
+    (
+        streamed_xid(version),
+        any::<i32>(),
+        namespace(),
+        string(),
+        replica_identity,
+        vec(column, 0..8),
+    )
+        .prop_map(
+            |(transaction_id, oid, namespace, name, replica_identity, columns)| RelationMessage {
+                transaction_id,
+                oid,
+                namespace,
+                name,
+                replica_identity,
+                columns,
+            },
+        )
+}
+
+fn message(options: ParseOptions) -> impl Strategy<Value = OwnedReplicationMessage> {
+    use OwnedReplicationMessage as M;
+    let version = options.version;
+
+    prop_oneof![
+        (lsn(), timestamp(), xid()).prop_map(|(final_lsn, timestamp, transaction_id)| {
+            M::Begin(BeginMessage {
+                final_lsn,
+                timestamp,
+                transaction_id,
+            })
+        }),
+        (
+            streamed_xid(version),
+            any::<bool>(),
+            lsn(),
+            string(),
+            vec(any::<u8>(), 0..32)
+        )
+            .prop_map(|(transaction_id, is_transactional, lsn, prefix, content)| {
+                M::Generic(OwnedGenericMessage {
+                    transaction_id,
+                    is_transactional,
+                    lsn,
+                    prefix,
+                    length: content.len() as i32,
+                    content,
+                })
+            }),
+        (lsn(), lsn(), timestamp()).prop_map(|(lsn, final_lsn, timestamp)| {
+            M::Commit(CommitMessage {
+                lsn,
+                final_lsn,
+                timestamp,
+            })
+        }),
+        (lsn(), string()).prop_map(|(lsn, name)| M::Origin(OriginMessage { lsn, name })),
+        relation(version).prop_map(M::Relation),
+        (streamed_xid(version), any::<i32>(), namespace(), string()).prop_map(
+            |(transaction_id, oid, namespace, name)| {
+                M::Type(TypeMessage {
+                    transaction_id,
+                    oid,
+                    namespace,
+                    name,
+                })
+            }
+        ),
+        (streamed_xid(version), any::<i32>(), tuple()).prop_map(|(transaction_id, oid, data)| {
+            M::Insert(OwnedInsertMessage {
+                transaction_id,
+                oid,
+                data,
+            })
+        }),
+        (
+            streamed_xid(version),
+            any::<i32>(),
+            option::of(tuple()),
+            option::of(tuple()),
+            tuple()
+        )
+            .prop_map(|(transaction_id, oid, key, old, new)| {
+                M::Update(OwnedUpdateMessage {
+                    transaction_id,
+                    oid,
+                    // Postgres sends either the key or the old tuple, never both.
+                    old: if key.is_some() { None } else { old },
+                    key,
+                    new,
+                })
+            }),
+        (streamed_xid(version), any::<i32>(), any::<bool>(), tuple()).prop_map(
+            |(transaction_id, oid, is_key, tuple)| {
+                M::Delete(OwnedDeleteMessage {
+                    transaction_id,
+                    oid,
+                    key: is_key.then(|| tuple.clone()),
+                    old: (!is_key).then_some(tuple),
+                })
+            }
+        ),
+        (
+            streamed_xid(version),
+            any::<bool>(),
+            any::<bool>(),
+            vec(any::<i32>(), 0..8)
+        )
+            .prop_map(|(transaction_id, is_cascade, is_restart_identity, oids)| {
+                M::Truncate(TruncateMessage {
+                    transaction_id,
+                    is_cascade,
+                    is_restart_identity,
+                    oids,
+                })
+            }),
+        (xid(), any::<bool>()).prop_map(|(transaction_id, is_first_segment)| {
+            M::StreamStart(StreamStartMessage {
+                transaction_id: Some(transaction_id),
+                is_first_segment,
+            })
+        }),
+        Just(M::StreamStop(StreamStopMessage {})),
+        (xid(), lsn(), lsn(), timestamp()).prop_map(
+            |(transaction_id, lsn, final_lsn, timestamp)| {
+                M::StreamCommit(StreamCommitMessage {
+                    transaction_id,
+                    lsn,
+                    final_lsn,
+                    timestamp,
+                })
+            }
+        ),
+        (xid(), xid(), parallel_abort(options)).prop_map(
+            |(transaction_id, subtransaction_id, parallel)| {
+                M::StreamAbort(StreamAbortMessage {
+                    transaction_id,
+                    subtransaction_id,
+                    abort_lsn: parallel.map(|(lsn, _)| lsn),
+                    abort_timestamp: parallel.map(|(_, timestamp)| timestamp),
+                })
+            }
+        ),
+        (lsn(), lsn(), timestamp(), xid(), string()).prop_map(
+            |(lsn, final_lsn, timestamp, transaction_id, gid)| {
+                M::BeginPrepare(BeginPrepareMessage {
+                    lsn,
+                    final_lsn,
+                    timestamp,
+                    transaction_id,
+                    gid,
+                })
+            }
+        ),
+        (lsn(), lsn(), timestamp(), xid(), string()).prop_map(
+            |(lsn, final_lsn, timestamp, transaction_id, gid)| {
+                M::Prepare(PrepareMessage {
+                    lsn,
+                    final_lsn,
+                    timestamp,
+                    transaction_id,
+                    gid,
+                })
+            }
+        ),
+        (lsn(), lsn(), timestamp(), xid(), string()).prop_map(
+            |(lsn, final_lsn, timestamp, transaction_id, gid)| {
+                M::CommitPrepared(CommitPreparedMessage {
+                    lsn,
+                    final_lsn,
+                    timestamp,
+                    transaction_id,
+                    gid,
+                })
+            }
+        ),
+        (lsn(), lsn(), timestamp(), timestamp(), xid(), string()).prop_map(
+            |(lsn, final_lsn, prepare_timestamp, timestamp, transaction_id, gid)| {
+                M::RollbackPrepared(RollbackPreparedMessage {
+                    lsn,
+                    final_lsn,
+                    prepare_timestamp,
+                    timestamp,
+                    transaction_id,
+                    gid,
+                })
+            }
+        ),
+        (lsn(), lsn(), timestamp(), xid(), string()).prop_map(
+            |(lsn, final_lsn, timestamp, transaction_id, gid)| {
+                M::StreamPrepare(StreamPrepareMessage {
+                    lsn,
+                    final_lsn,
+                    timestamp,
+                    transaction_id,
+                    gid,
+                })
+            }
+        ),
+    ]
+}
+
+fn versioned_message() -> impl Strategy<Value = (ParseOptions, OwnedReplicationMessage)> {
+    options().prop_flat_map(|options| (Just(options), message(options)))
+}
+
+proptest! {
+    #[test]
+    fn parse_is_inverse_of_encode((options, message) in versioned_message()) {
+        let mut buf = Vec::new();
+        message.encode(&mut buf).unwrap();
+
+        let parsed = ReplicationMessage::parse_with_options(&buf, options).unwrap();
+        prop_assert_eq!(parsed.to_owned(), message);
+    }
+
+    #[test]
+    fn encode_is_inverse_of_parse((options, message) in versioned_message()) {
+        let mut buf = Vec::new();
+        message.encode(&mut buf).unwrap();
+
+        let mut encoded = Vec::new();
+        ReplicationMessage::parse_with_options(&buf, options).unwrap().encode(&mut encoded).unwrap();
+        prop_assert_eq!(encoded, buf);
+    }
+}
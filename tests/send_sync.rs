@@ -0,0 +1,35 @@
+use postgres_replication_types::*;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn public_types_are_send_and_sync() {
+    assert_send_sync::<ReplicationMessage<'static>>();
+    assert_send_sync::<OwnedReplicationMessage>();
+    assert_send_sync::<TupleData<'static>>();
+    assert_send_sync::<OwnedTupleData>();
+    assert_send_sync::<ChangeEvent<'static>>();
+    assert_send_sync::<NamedChangeEvent>();
+    assert_send_sync::<TruncateEvent>();
+    assert_send_sync::<CopyData<'static>>();
+    assert_send_sync::<MessageIter<'static>>();
+    assert_send_sync::<RelationCache>();
+    assert_send_sync::<StreamReassembler>();
+    assert_send_sync::<ReplicationStream>();
+    assert_send_sync::<ReplicationProgress>();
+    assert_send_sync::<ChangeStats>();
+    assert_send_sync::<TypeRegistry<i64>>();
+    assert_send_sync::<ParseError>();
+    assert_send_sync::<EncodeError>();
+    assert_send_sync::<ValueDecodeError>();
+    assert_send_sync::<ValidationError>();
+    assert_send_sync::<ReplicationStreamError>();
+}
+
+#[test]
+fn parsed_messages_can_be_sent_to_another_thread() {
+    let message = ReplicationMessage::parse(b"E").unwrap().to_owned();
+
+    let message = std::thread::spawn(move || message).join().unwrap();
+    assert!(matches!(message, OwnedReplicationMessage::StreamStop(_)));
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "serde")]
+
+use postgres_replication_types::*;
+
+#[test]
+fn owned_insert_round_trips_through_json() {
+    let buf = [
+        b'I', 0, 0, 0x40, 0x01, b'N', 0, 4, b't', 0, 0, 0, 2, b'h', b'i', b'b', 0, 0, 0, 3, b'a',
+        b'b', b'c', b'n', b'u',
+    ];
+    let message = ReplicationMessage::parse(&buf).unwrap().to_owned();
+
+    let json = serde_json::to_string(&message).unwrap();
+    let decoded: OwnedReplicationMessage = serde_json::from_str(&json).unwrap();
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+
+    let OwnedReplicationMessage::Insert(insert) = decoded else {
+        panic!("expected an insert");
+    };
+    assert_eq!(insert.data.columns[0].as_text(), Some("hi"));
+    assert_eq!(insert.data.columns[1].as_binary(), Some(&b"abc"[..]));
+    assert!(insert.data.columns[2].is_null());
+    assert!(insert.data.columns[3].is_unchanged_toast());
+}
+
+#[test]
+fn owned_begin_round_trips_through_json() {
+    let json = r#"{"type":"begin","final_lsn":"16/B374D848","timestamp":"2024-01-02T03:04:05.123456Z","transaction_id":5}"#;
+
+    let decoded: OwnedReplicationMessage = serde_json::from_str(json).unwrap();
+    let OwnedReplicationMessage::Begin(begin) = &decoded else {
+        panic!("expected a begin");
+    };
+    assert_eq!(begin.final_lsn, Lsn(0x16_B374_D848));
+    assert_eq!(begin.transaction_id, Xid(5));
+    assert_eq!(serde_json::to_string(&decoded).unwrap(), json);
+}
@@ -0,0 +1,69 @@
+use postgres_replication_types::*;
+
+fn row() -> TupleData<'static> {
+    TupleData {
+        columns: vec![
+            TupleDataColumn {
+                kind: TupleDataKind::Text("abc".into()),
+            },
+            TupleDataColumn {
+                kind: TupleDataKind::Null,
+            },
+            TupleDataColumn {
+                kind: TupleDataKind::Binary(b"12345"),
+            },
+        ],
+    }
+}
+
+#[test]
+fn mixed_changes_are_tallied_per_relation() {
+    let mut stats = ChangeStats::new();
+    for message in [
+        ReplicationMessage::Insert(InsertMessage::builder().oid(1).data(row()).build()),
+        ReplicationMessage::Insert(InsertMessage::builder().oid(2).data(row()).build()),
+        ReplicationMessage::Update(
+            UpdateMessage::builder()
+                .oid(1)
+                .old(row())
+                .new_tuple(row())
+                .build(),
+        ),
+        ReplicationMessage::Delete(
+            DeleteMessage::builder()
+                .oid(1)
+                .key(TupleData::builder().text("k").build())
+                .build(),
+        ),
+        ReplicationMessage::Truncate(TruncateMessage {
+            transaction_id: None,
+            is_cascade: false,
+            is_restart_identity: false,
+            oids: vec![1, 3],
+        }),
+        ReplicationMessage::StreamStop(StreamStopMessage {}),
+    ] {
+        stats.observe(&message);
+    }
+
+    let snapshot = stats.snapshot();
+    assert_eq!(snapshot.len(), 3);
+    assert_eq!(
+        snapshot[&1],
+        RelationStats {
+            inserts: 1,
+            updates: 1,
+            deletes: 1,
+            truncates: 1,
+            tuple_bytes: 3 * 8 + 1,
+        }
+    );
+    assert_eq!(stats.get(2).unwrap().inserts, 1);
+    assert_eq!(stats.get(2).unwrap().tuple_bytes, 8);
+    assert_eq!(stats.get(3).unwrap().truncates, 1);
+    assert_eq!(stats.total().inserts, 2);
+    assert_eq!(stats.total().truncates, 2);
+
+    stats.clear();
+    assert!(stats.snapshot().is_empty());
+}
@@ -0,0 +1,97 @@
+use postgres_replication_types::*;
+
+fn start(xid: u32) -> ReplicationMessage<'static> {
+    ReplicationMessage::StreamStart(StreamStartMessage {
+        transaction_id: Some(Xid(xid)),
+        is_first_segment: true,
+    })
+}
+
+fn stop() -> ReplicationMessage<'static> {
+    ReplicationMessage::StreamStop(StreamStopMessage {})
+}
+
+fn insert(xid: u32, oid: i32) -> ReplicationMessage<'static> {
+    ReplicationMessage::Insert(InsertMessage {
+        transaction_id: Some(Xid(xid)),
+        oid,
+        data: TupleData { columns: vec![] },
+    })
+}
+
+fn commit(xid: u32) -> ReplicationMessage<'static> {
+    ReplicationMessage::StreamCommit(StreamCommitMessage {
+        transaction_id: Xid(xid),
+        lsn: Lsn(1),
+        final_lsn: Lsn(2),
+        timestamp: pg_timestamp_from_micros(0),
+    })
+}
+
+fn oids(batch: &[OwnedReplicationMessage]) -> Vec<i32> {
+    batch
+        .iter()
+        .filter_map(|message| match message {
+            OwnedReplicationMessage::Insert(insert) => Some(insert.oid),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn interleaved_transactions_are_reassembled() {
+    let mut reassembler = StreamReassembler::new();
+    for message in [
+        start(1),
+        insert(1, 10),
+        stop(),
+        start(2),
+        insert(2, 20),
+        stop(),
+        start(1),
+        insert(1, 11),
+        stop(),
+        start(2),
+        insert(2, 21),
+        stop(),
+    ] {
+        assert!(reassembler.push(&message).is_none());
+    }
+    assert_eq!(reassembler.in_progress(), 2);
+
+    let batch = reassembler.push(&commit(2)).unwrap();
+    assert_eq!(oids(&batch), [20, 21]);
+    assert!(matches!(
+        batch.last(),
+        Some(OwnedReplicationMessage::StreamCommit(_))
+    ));
+
+    let batch = reassembler.push(&commit(1)).unwrap();
+    assert_eq!(oids(&batch), [10, 11]);
+    assert_eq!(reassembler.in_progress(), 0);
+}
+
+#[test]
+fn aborted_transaction_is_discarded() {
+    let mut reassembler = StreamReassembler::new();
+    for message in [
+        start(1),
+        insert(1, 10),
+        stop(),
+        start(2),
+        insert(2, 20),
+        stop(),
+    ] {
+        reassembler.push(&message);
+    }
+
+    reassembler.push(&ReplicationMessage::StreamAbort(StreamAbortMessage {
+        transaction_id: Xid(2),
+        subtransaction_id: Xid(2),
+        abort_lsn: None,
+        abort_timestamp: None,
+    }));
+    assert_eq!(reassembler.in_progress(), 1);
+    assert!(reassembler.push(&commit(2)).is_none());
+    assert_eq!(oids(&reassembler.push(&commit(1)).unwrap()), [10]);
+}
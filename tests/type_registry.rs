@@ -0,0 +1,79 @@
+use postgres_replication_types::*;
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
+    Text(String),
+}
+
+macro_rules! from {
+    ($($ty:ty => $variant:ident),*) => {
+        $(impl From<$ty> for Value {
+            fn from(value: $ty) -> Self {
+                Self::$variant(value.into())
+            }
+        })*
+    };
+}
+
+from!(bool => Bool, i16 => Int, i32 => Int, i64 => Int, f32 => Float, f64 => Float, String => Text);
+
+fn text(value: &str) -> TupleDataColumn<'_> {
+    TupleDataColumn {
+        kind: TupleDataKind::Text(value.into()),
+    }
+}
+
+#[test]
+fn builtins_are_decoded_by_oid() {
+    let registry = TypeRegistry::<Value>::with_builtins();
+
+    assert_eq!(
+        registry.decode(BOOL_OID, &text("t")).unwrap(),
+        Value::Bool(true)
+    );
+    assert_eq!(
+        registry.decode(INT4_OID, &text("42")).unwrap(),
+        Value::Int(42)
+    );
+    assert_eq!(
+        registry.decode(OID_OID, &text("4294967295")).unwrap(),
+        Value::Int(4_294_967_295)
+    );
+    assert_eq!(
+        registry.decode(FLOAT8_OID, &text("1.5")).unwrap(),
+        Value::Float(1.5)
+    );
+    assert_eq!(
+        registry.decode(VARCHAR_OID, &text("alice")).unwrap(),
+        Value::Text("alice".into())
+    );
+    assert!(matches!(
+        registry.decode(BYTEA_OID, &text("\\x00")),
+        Err(ValueDecodeError::UnknownType { .. })
+    ));
+}
+
+#[test]
+fn builtins_are_named() {
+    let registry = TypeRegistry::<Value>::with_builtins();
+
+    for oid in [
+        BOOL_OID,
+        INT2_OID,
+        INT4_OID,
+        INT8_OID,
+        OID_OID,
+        FLOAT4_OID,
+        FLOAT8_OID,
+        NAME_OID,
+        TEXT_OID,
+        BPCHAR_OID,
+        VARCHAR_OID,
+    ] {
+        assert!(registry.contains(oid), "{oid}");
+        assert!(builtin_type_name(oid).is_some(), "{oid}");
+    }
+}
@@ -0,0 +1,27 @@
+use postgres_replication_types::*;
+
+#[test]
+fn changed_columns_of_a_full_update() {
+    let old = TupleData::builder()
+        .text("1")
+        .text("alice")
+        .text("alice@example.com")
+        .text("big")
+        .build();
+    let new = TupleData::builder()
+        .text("1")
+        .text("bob")
+        .text("bob@example.com")
+        .unchanged_toast()
+        .build();
+    let mut update = UpdateMessage::builder()
+        .oid(16385)
+        .old(old)
+        .new_tuple(new)
+        .build();
+
+    assert_eq!(update.changed_columns(), Some(vec![1, 2]));
+
+    update.old = None;
+    assert_eq!(update.changed_columns(), None);
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "uuid")]
+
+use postgres_replication_types::*;
+
+fn text(value: &str) -> TupleDataColumn<'_> {
+    TupleDataColumn {
+        kind: TupleDataKind::Text(value.into()),
+    }
+}
+
+fn binary(value: &[u8]) -> TupleDataColumn<'_> {
+    TupleDataColumn {
+        kind: TupleDataKind::Binary(value),
+    }
+}
+
+#[test]
+fn uuids_are_decoded_from_text_and_binary() {
+    let uuid = text("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11")
+        .as_uuid()
+        .unwrap();
+
+    assert_eq!(uuid.as_u128(), 0xa0eebc99_9c0b_4ef8_bb6d_6bb9bd380a11);
+    assert_eq!(binary(uuid.as_bytes()).as_uuid().unwrap(), uuid);
+}
+
+#[test]
+fn malformed_uuids_are_rejected() {
+    assert!(matches!(
+        text("a0eebc99-9c0b").as_uuid(),
+        Err(ValueDecodeError::ParseUuid(_))
+    ));
+    assert!(matches!(
+        binary(&[1, 2]).as_uuid(),
+        Err(ValueDecodeError::UnexpectedLength {
+            expected: 16,
+            actual: 2
+        })
+    ));
+}
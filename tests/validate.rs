@@ -0,0 +1,126 @@
+use postgres_replication_types::*;
+
+fn nulls(count: usize) -> TupleData<'static> {
+    TupleData {
+        columns: vec![
+            TupleDataColumn {
+                kind: TupleDataKind::Null
+            };
+            count
+        ],
+    }
+}
+
+fn update(key: Option<usize>, old: Option<usize>, new: usize) -> ReplicationMessage<'static> {
+    ReplicationMessage::Update(UpdateMessage {
+        transaction_id: None,
+        oid: 1,
+        key: key.map(nulls),
+        old: old.map(nulls),
+        new: nulls(new),
+    })
+}
+
+#[test]
+fn consistent_messages_are_valid() {
+    for buf in [&b"E"[..], &[b'I', 0, 0, 0, 1, b'N', 0, 1, b'n']] {
+        assert_eq!(ReplicationMessage::parse(buf).unwrap().validate(), Ok(()));
+    }
+    assert_eq!(update(None, Some(3), 3).validate(), Ok(()));
+}
+
+#[test]
+fn generic_message_violations() {
+    let generic = GenericMessage {
+        transaction_id: Some(Xid(5)),
+        is_transactional: false,
+        lsn: Lsn(1),
+        prefix: "p".into(),
+        length: 2,
+        content: b"ab",
+    };
+    assert_eq!(
+        ReplicationMessage::Generic(generic.clone()).validate(),
+        Err(ValidationError::NonTransactionalXid)
+    );
+
+    let generic = GenericMessage {
+        transaction_id: None,
+        length: 3,
+        ..generic
+    };
+    assert_eq!(
+        ReplicationMessage::Generic(generic).validate(),
+        Err(ValidationError::GenericLengthMismatch {
+            length: 3,
+            actual: 2
+        })
+    );
+}
+
+#[test]
+fn final_lsn_before_lsn() {
+    let commit = ReplicationMessage::Commit(CommitMessage {
+        lsn: Lsn(10),
+        final_lsn: Lsn(5),
+        timestamp: pg_timestamp_from_micros(0),
+    });
+
+    assert_eq!(
+        commit.validate(),
+        Err(ValidationError::FinalLsnBeforeLsn {
+            lsn: Lsn(10),
+            final_lsn: Lsn(5)
+        })
+    );
+}
+
+#[test]
+fn tuple_violations() {
+    let insert = ReplicationMessage::Insert(InsertMessage {
+        transaction_id: None,
+        oid: 1,
+        data: nulls(1665),
+    });
+    assert_eq!(
+        insert.validate(),
+        Err(ValidationError::TooManyColumns { count: 1665 })
+    );
+
+    assert_eq!(
+        update(None, Some(2), 3).validate(),
+        Err(ValidationError::TupleLengthMismatch { old: 2, new: 3 })
+    );
+    assert_eq!(
+        update(Some(3), Some(3), 3).validate(),
+        Err(ValidationError::KeyAndOldTuple)
+    );
+}
+
+#[test]
+fn incomplete_abort_info() {
+    let abort = ReplicationMessage::StreamAbort(StreamAbortMessage {
+        transaction_id: Xid(1),
+        subtransaction_id: Xid(1),
+        abort_lsn: Some(Lsn(1)),
+        abort_timestamp: None,
+    });
+
+    assert_eq!(abort.validate(), Err(ValidationError::IncompleteAbortInfo));
+}
+
+#[test]
+fn gid_too_long() {
+    let prepare = ReplicationMessage::Prepare(PrepareMessage {
+        lsn: Lsn(1),
+        final_lsn: Lsn(2),
+        timestamp: pg_timestamp_from_micros(0),
+        transaction_id: Xid(1),
+        gid: "x".repeat(MAX_GID_LENGTH + 1),
+    });
+
+    assert!(matches!(
+        prepare.validate(),
+        Err(ValidationError::GidTooLong(_))
+    ));
+}
@@ -0,0 +1,138 @@
+use postgres_replication_types::*;
+
+fn text(value: &str) -> TupleDataColumn<'_> {
+    TupleDataColumn {
+        kind: TupleDataKind::Text(value.into()),
+    }
+}
+
+fn binary(value: &[u8]) -> TupleDataColumn<'_> {
+    TupleDataColumn {
+        kind: TupleDataKind::Binary(value),
+    }
+}
+
+#[test]
+fn integers_are_decoded_from_text_and_binary() {
+    assert_eq!(text("42").as_i32().unwrap(), 42);
+    assert_eq!(binary(&[0, 0, 0, 42]).as_i32().unwrap(), 42);
+    assert_eq!(text("-9000000000").as_i64().unwrap(), -9_000_000_000);
+    assert_eq!(binary(&42i64.to_be_bytes()).as_i64().unwrap(), 42);
+
+    assert!(matches!(
+        binary(&[0, 42]).as_i32(),
+        Err(ValueDecodeError::UnexpectedLength {
+            expected: 4,
+            actual: 2
+        })
+    ));
+    assert!(matches!(
+        text("x").as_i32(),
+        Err(ValueDecodeError::ParseInt(_))
+    ));
+    assert!(matches!(
+        TupleDataColumn {
+            kind: TupleDataKind::Null
+        }
+        .as_i64(),
+        Err(ValueDecodeError::Null)
+    ));
+}
+
+#[test]
+fn booleans_are_decoded_from_text_and_binary() {
+    assert!(text("t").as_bool().unwrap());
+    assert!(!text("f").as_bool().unwrap());
+    assert!(binary(&[1]).as_bool().unwrap());
+    assert!(!binary(&[0]).as_bool().unwrap());
+
+    assert!(matches!(
+        text("true").as_bool(),
+        Err(ValueDecodeError::InvalidValue { type_name: "bool" })
+    ));
+    assert!(binary(&[2]).as_bool().is_err());
+    assert!(matches!(
+        binary(&[]).as_bool(),
+        Err(ValueDecodeError::UnexpectedLength {
+            expected: 1,
+            actual: 0
+        })
+    ));
+}
+
+#[test]
+fn timestamps_are_decoded_from_text_and_binary() {
+    let timestamp = text("2024-01-02 03:04:05.123456+00")
+        .as_timestamptz()
+        .unwrap();
+    assert_eq!(timestamp.to_rfc3339(), "2024-01-02T03:04:05.123456+00:00");
+    assert_eq!(
+        text("2024-01-02 03:04:05+05:30")
+            .as_timestamptz()
+            .unwrap()
+            .to_rfc3339(),
+        "2024-01-01T21:34:05+00:00"
+    );
+    assert_eq!(
+        binary(&0i64.to_be_bytes())
+            .as_timestamptz()
+            .unwrap()
+            .to_rfc3339(),
+        "2000-01-01T00:00:00+00:00"
+    );
+    assert!(text("infinity").as_timestamptz().is_err());
+    assert!(binary(&i64::MAX.to_be_bytes()).as_timestamptz().is_err());
+}
+
+#[test]
+fn timestamps_before_the_postgres_epoch_are_decoded() {
+    assert_eq!(
+        binary(&(-1i64).to_be_bytes())
+            .as_timestamptz()
+            .unwrap()
+            .to_rfc3339(),
+        "1999-12-31T23:59:59.999999+00:00"
+    );
+    assert_eq!(
+        binary(&(-946_684_800_000_000i64).to_be_bytes())
+            .as_timestamptz()
+            .unwrap()
+            .to_rfc3339(),
+        "1970-01-01T00:00:00+00:00"
+    );
+}
+
+#[test]
+fn arrays_are_decoded_from_text() {
+    assert_eq!(
+        text("{1,2,3}").as_array::<i32>().unwrap(),
+        [Some(1), Some(2), Some(3)]
+    );
+    assert_eq!(
+        text(r#"{"a,b","c"}"#).as_text_array().unwrap(),
+        [Some("a,b".into()), Some("c".into())]
+    );
+    assert_eq!(
+        text("{1,NULL,3}").as_array::<i64>().unwrap(),
+        [Some(1), None, Some(3)]
+    );
+    assert_eq!(text("{}").as_array::<i32>().unwrap(), []);
+
+    assert!(text("{{1,2},{3,4}}").as_array::<i32>().is_err());
+    assert!(text("{1,2").as_array::<i32>().is_err());
+}
+
+#[test]
+fn arrays_are_decoded_from_binary() {
+    // One dimension of 3 `int4` elements starting at index 1, the second one NULL.
+    let mut buf = Vec::new();
+    for word in [1i32, 1, 23, 3, 1, 4, 5, -1, 4, 6] {
+        buf.extend_from_slice(&word.to_be_bytes());
+    }
+
+    assert_eq!(
+        binary(&buf).as_array::<i32>().unwrap(),
+        [Some(5), None, Some(6)]
+    );
+    assert!(binary(&buf[..buf.len() - 1]).as_array::<i32>().is_err());
+}
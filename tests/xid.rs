@@ -0,0 +1,27 @@
+use postgres_replication_types::*;
+
+#[test]
+fn high_xid_round_trips() {
+    let buf = [
+        b'B', 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0xff, 0xff, 0xff, 0xf0,
+    ];
+
+    let message = ReplicationMessage::parse(&buf).unwrap();
+    let ReplicationMessage::Begin(begin) = &message else {
+        panic!("expected a begin");
+    };
+    assert_eq!(begin.transaction_id, Xid(0xffff_fff0));
+    assert_eq!(begin.transaction_id.to_string(), "4294967280");
+
+    let mut encoded = Vec::new();
+    message.encode(&mut encoded).unwrap();
+    assert_eq!(encoded, buf);
+}
+
+#[test]
+fn xids_are_compared_modulo_wraparound() {
+    assert!(Xid(0xffff_fff0).precedes(Xid(5)));
+    assert!(Xid(5).follows(Xid(0xffff_fff0)));
+    assert!(Xid(2).precedes(Xid(0xffff_fff0)));
+    assert!(!Xid(7).precedes(Xid(7)));
+}